@@ -5,4 +5,9 @@ set -ex
 # make sure contract builds are up to date
 soroban contract build
 soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_vrf_direct_funding_proxy.wasm
+soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_functions_proxy.wasm
+soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_message_receiver.wasm
+soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_automation_proxy.wasm
+soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_randomness_pool.wasm
+soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_consumer_adapter.wasm
 soroban contract optimize --wasm target/wasm32-unknown-unknown/release/relink_vrf_direct_funding_consumer.wasm
@@ -13,5 +13,5 @@ CONSUMER_ADDRESS=$(soroban contract deploy "$@" --wasm target/wasm32-unknown-unk
 echo "export CONSUMER_ADDRESS=$CONSUMER_ADDRESS"
 
 # initialize consumer
-soroban contract invoke --id $CONSUMER_ADDRESS "$@" -- initialize --proxy $PROXY_ADDRESS --threshold 0 --oracles '[]'
+soroban contract invoke --id $CONSUMER_ADDRESS "$@" -- initialize_insecure --owner alice --proxy $PROXY_ADDRESS
 echo "# consumer initialized"
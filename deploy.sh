@@ -32,5 +32,5 @@ soroban contract invoke --id $PROXY_ADDRESS "$@" -- add_backend_whitelist --addr
 echo "# identity \"backend\" added to proxy backend whitelist"
 
 # initialize consumer
-soroban contract invoke --id $CONSUMER_ADDRESS "$@" -- initialize --proxy $PROXY_ADDRESS --threshold 0 --oracles '[]'
+soroban contract invoke --id $CONSUMER_ADDRESS "$@" -- initialize_insecure --owner alice --proxy $PROXY_ADDRESS
 echo "# consumer initialized"
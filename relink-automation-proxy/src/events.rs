@@ -0,0 +1,100 @@
+use soroban_sdk::{Address, Env};
+
+use relink::events::{
+    FeeSetEvent, UpkeepCancelledEvent, UpkeepFundedEvent, UpkeepPerformedEvent,
+    UpkeepRegisteredEvent, WhitelistAddedEvent, WhitelistRemovedEvent, EVENT_VERSION, FEE_SET,
+    UPKEEP_CANCELLED, UPKEEP_FUNDED, UPKEEP_PERFORMED, UPKEEP_REGISTERED, WHITELIST_ADDED,
+    WHITELIST_REMOVED,
+};
+
+pub(crate) fn upkeep_registered(
+    env: &Env,
+    id: u64,
+    admin: Address,
+    target: Address,
+    interval: u32,
+    max_fee: i128,
+) {
+    let topics = (UPKEEP_REGISTERED, id, admin.clone());
+    env.events().publish(
+        topics,
+        UpkeepRegisteredEvent {
+            version: EVENT_VERSION,
+            id,
+            admin,
+            target,
+            interval,
+            max_fee,
+        },
+    );
+}
+
+pub(crate) fn upkeep_funded(env: &Env, id: u64, amount: i128) {
+    let topics = (UPKEEP_FUNDED, id);
+    env.events().publish(
+        topics,
+        UpkeepFundedEvent {
+            version: EVENT_VERSION,
+            id,
+            amount,
+        },
+    );
+}
+
+pub(crate) fn upkeep_performed(env: &Env, id: u64, backend: Address, fee: i128) {
+    let topics = (UPKEEP_PERFORMED, id, backend.clone());
+    env.events().publish(
+        topics,
+        UpkeepPerformedEvent {
+            version: EVENT_VERSION,
+            id,
+            backend,
+            fee,
+        },
+    );
+}
+
+pub(crate) fn upkeep_cancelled(env: &Env, id: u64, refund: i128) {
+    let topics = (UPKEEP_CANCELLED, id);
+    env.events().publish(
+        topics,
+        UpkeepCancelledEvent {
+            version: EVENT_VERSION,
+            id,
+            refund,
+        },
+    );
+}
+
+pub(crate) fn fee_set(env: &Env, fee: i128) {
+    let topics = (FEE_SET,);
+    env.events().publish(
+        topics,
+        FeeSetEvent {
+            version: EVENT_VERSION,
+            fee,
+        },
+    );
+}
+
+pub(crate) fn whitelist_address_added(env: &Env, address: Address) {
+    let topics = (WHITELIST_ADDED, address.clone());
+    env.events().publish(
+        topics,
+        WhitelistAddedEvent {
+            version: EVENT_VERSION,
+            address,
+        },
+    );
+}
+
+pub(crate) fn whitelist_address_removed(env: &Env, address: Address) {
+    let topics = (WHITELIST_REMOVED, address.clone());
+    env.events().publish(
+        topics,
+        WhitelistRemovedEvent {
+            version: EVENT_VERSION,
+            address,
+        },
+    );
+}
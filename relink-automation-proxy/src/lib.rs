@@ -0,0 +1,173 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+use relink::{
+    confirmed_owner, impl_confirmed_owner, AutomationTargetClient, ConfirmedOwner, Error,
+};
+
+pub use storage::Upkeep;
+
+pub mod events;
+mod storage;
+mod test;
+
+#[contract]
+pub struct RelinkAutomationProxy;
+
+impl_confirmed_owner!(RelinkAutomationProxy);
+
+#[contractimpl]
+impl RelinkAutomationProxy {
+    /// Initialize contract by setting the owner address, token to use and fee per execution.
+    pub fn initialize(env: Env, owner: Address, token: Address, fee: i128) {
+        confirmed_owner::init(&env, &owner);
+        storage::set_token(&env, &token);
+        storage::set_fee(&env, &fee);
+    }
+
+    /// Add an address to the backend whitelist.
+    pub fn add_backend_whitelist(env: Env, address: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::add_whitelist(&env, address.clone());
+        events::whitelist_address_added(&env, address.clone());
+    }
+
+    /// Remove an address from the backend whitelist.
+    pub fn remove_backend_whitelist(env: Env, address: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::remove_whitelist(&env, address.clone());
+        events::whitelist_address_removed(&env, address.clone());
+    }
+
+    /// Update the fee paid to the backend for each execution of an upkeep.
+    pub fn set_fee(env: Env, fee: i128) {
+        confirmed_owner::require_owner(&env);
+        storage::set_fee(&env, &fee);
+        events::fee_set(&env, fee);
+    }
+
+    /// Get the current fee paid to the backend for each execution of an upkeep.
+    pub fn get_fee(env: Env) -> i128 {
+        storage::get_fee(&env)
+    }
+
+    /// Register a new upkeep, funded with `balance` tokens transferred from the admin.
+    pub fn register_upkeep(
+        env: Env,
+        admin: Address,
+        target: Address,
+        interval: u32,
+        balance: i128,
+        max_fee: i128,
+    ) -> u64 {
+        // require authorization by admin
+        admin.require_auth();
+        if interval == 0 {
+            panic!("interval must be positive");
+        }
+        if balance < 0 {
+            panic!("negative amount");
+        }
+
+        // transfer funding to this contract
+        let client = token::Client::new(&env, &storage::get_token(&env));
+        client.transfer(&admin, &env.current_contract_address(), &balance);
+
+        let id = storage::get_and_increment_upkeep_id(&env);
+        storage::set_upkeep(
+            &env,
+            id,
+            &Upkeep {
+                admin: admin.clone(),
+                target: target.clone(),
+                interval,
+                balance,
+                max_fee,
+                last_performed: env.ledger().sequence(),
+            },
+        );
+        events::upkeep_registered(&env, id, admin, target, interval, max_fee);
+        events::upkeep_funded(&env, id, balance);
+        id
+    }
+
+    /// Add funds to an existing upkeep. Anyone can fund an upkeep.
+    pub fn add_funds(env: Env, from: Address, id: u64, amount: i128) -> Result<(), Error> {
+        // require authorization by sender
+        from.require_auth();
+        if amount < 0 {
+            panic!("negative amount");
+        }
+        let mut upkeep = storage::get_upkeep(&env, id)?;
+
+        // transfer funding to this contract
+        let client = token::Client::new(&env, &storage::get_token(&env));
+        client.transfer(&from, &env.current_contract_address(), &amount);
+
+        upkeep.balance = upkeep.balance.checked_add(amount).unwrap();
+        storage::set_upkeep(&env, id, &upkeep);
+        events::upkeep_funded(&env, id, amount);
+        Ok(())
+    }
+
+    /// Cancel an upkeep and refund the remaining balance to its admin.
+    pub fn cancel_upkeep(env: Env, id: u64) -> Result<(), Error> {
+        let upkeep = storage::get_upkeep(&env, id)?;
+        // only the admin of the upkeep can cancel it
+        upkeep.admin.require_auth();
+        storage::remove_upkeep(&env, id);
+
+        // refund remaining balance
+        let client = token::Client::new(&env, &storage::get_token(&env));
+        client.transfer(
+            &env.current_contract_address(),
+            &upkeep.admin,
+            &upkeep.balance,
+        );
+        events::upkeep_cancelled(&env, id, upkeep.balance);
+        Ok(())
+    }
+
+    /// Get the state of an upkeep.
+    pub fn get_upkeep(env: Env, id: u64) -> Result<Upkeep, Error> {
+        storage::get_upkeep(&env, id)
+    }
+
+    /// Entry point for backends to execute an upkeep that is due.
+    pub fn perform_upkeep(env: Env, backend: Address, id: u64) -> Result<(), Error> {
+        // only backends on the whitelist are allowed to call this
+        backend.require_auth();
+        storage::is_whitelisted(&env, backend.clone())?;
+        let mut upkeep = storage::get_upkeep(&env, id)?;
+
+        // check eligibility
+        let sequence = env.ledger().sequence();
+        if sequence < upkeep.last_performed.saturating_add(upkeep.interval) {
+            return Err(Error::UpkeepNotDue);
+        }
+        let fee = storage::get_fee(&env);
+        if fee > upkeep.max_fee {
+            return Err(Error::MaxFeeExceeded);
+        }
+        if upkeep.balance < fee {
+            return Err(Error::InsufficientFunds);
+        }
+
+        // deduct the fee before calling out to the target
+        upkeep.balance -= fee;
+        upkeep.last_performed = sequence;
+        storage::set_upkeep(&env, id, &upkeep);
+
+        // call the target contract
+        let target_client = AutomationTargetClient::new(&env, &upkeep.target);
+        target_client.perform(&id);
+
+        // pay the backend
+        let client = token::Client::new(&env, &storage::get_token(&env));
+        client.transfer(&env.current_contract_address(), &backend, &fee);
+
+        events::upkeep_performed(&env, id, backend, fee);
+        Ok(())
+    }
+}
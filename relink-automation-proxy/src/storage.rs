@@ -0,0 +1,113 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use relink::Error;
+
+const UPKEEP_BUMP_AMOUNT: u32 = 518400; // 30 days
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Upkeep {
+    /// Account that registered the upkeep, receives the remaining balance on cancellation.
+    pub admin: Address,
+    /// Contract implementing `AutomationTarget`.
+    pub target: Address,
+    /// Minimum number of ledgers between two executions.
+    pub interval: u32,
+    /// Remaining funding used to pay the execution fees.
+    pub balance: i128,
+    /// Maximum fee the admin is willing to pay per execution.
+    pub max_fee: i128,
+    /// Ledger sequence of the last execution, or of the registration.
+    pub last_performed: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKeyAutomationProxy {
+    Token,
+    Fee,
+    NextUpkeepId,
+    Whitelist(Address),
+    Upkeep(u64),
+}
+
+pub fn set_token(env: &Env, token: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKeyAutomationProxy::Token, &token);
+}
+
+pub fn get_token(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get::<_, Address>(&DataKeyAutomationProxy::Token)
+        .expect("not initialized")
+}
+
+pub fn set_fee(env: &Env, fee: &i128) {
+    env.storage()
+        .instance()
+        .set(&DataKeyAutomationProxy::Fee, fee);
+}
+
+pub fn get_fee(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get::<_, i128>(&DataKeyAutomationProxy::Fee)
+        .unwrap()
+}
+
+pub fn get_and_increment_upkeep_id(env: &Env) -> u64 {
+    // get value, default to zero
+    let value = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKeyAutomationProxy::NextUpkeepId)
+        .unwrap_or(0);
+    // store value increment by one
+    env.storage().instance().set::<_, u64>(
+        &DataKeyAutomationProxy::NextUpkeepId,
+        &value.checked_add(1).unwrap(),
+    );
+    // return original value
+    value
+}
+
+pub fn add_whitelist(env: &Env, address: Address) {
+    let key = DataKeyAutomationProxy::Whitelist(address);
+    env.storage().instance().set(&key, &());
+}
+
+pub fn remove_whitelist(env: &Env, address: Address) {
+    let key = DataKeyAutomationProxy::Whitelist(address);
+    env.storage().instance().remove(&key);
+}
+
+pub fn is_whitelisted(env: &Env, address: Address) -> Result<(), Error> {
+    let key = DataKeyAutomationProxy::Whitelist(address);
+    env.storage()
+        .instance()
+        .get(&key)
+        .ok_or(Error::UnauthorizedBackend)
+}
+
+pub fn set_upkeep(env: &Env, id: u64, upkeep: &Upkeep) {
+    let key = DataKeyAutomationProxy::Upkeep(id);
+    env.storage().persistent().set(&key, upkeep);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, UPKEEP_BUMP_AMOUNT, UPKEEP_BUMP_AMOUNT);
+}
+
+pub fn get_upkeep(env: &Env, id: u64) -> Result<Upkeep, Error> {
+    let key = DataKeyAutomationProxy::Upkeep(id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::UpkeepUnknown)
+}
+
+pub fn remove_upkeep(env: &Env, id: u64) {
+    let key = DataKeyAutomationProxy::Upkeep(id);
+    env.storage().persistent().remove(&key);
+}
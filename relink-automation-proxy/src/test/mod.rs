@@ -0,0 +1,4 @@
+#![cfg(test)]
+
+mod proxy;
+mod test_target;
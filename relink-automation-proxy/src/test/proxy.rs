@@ -0,0 +1,221 @@
+extern crate std;
+
+use std::println;
+
+use soroban_sdk::{
+    testutils::{Address as AddressTestTrait, Events},
+    token, vec, Address, Env, IntoVal,
+};
+
+use relink::events::{UpkeepPerformedEvent, EVENT_VERSION, UPKEEP_PERFORMED};
+use relink::testutils::advance_ledger_sequence;
+use relink::Error;
+
+use crate::test::test_target::{TestTarget, TestTargetClient};
+use crate::{RelinkAutomationProxy, RelinkAutomationProxyClient, Upkeep};
+
+#[allow(dead_code)]
+struct Setup<'a> {
+    env: Env,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    token_owner: Address,
+    proxy: RelinkAutomationProxyClient<'a>,
+    proxy_owner: Address,
+    target: TestTargetClient<'a>,
+    backend: Address,
+}
+
+impl Setup<'_> {
+    fn new() -> Self {
+        let env: Env = Env::default();
+        env.mock_all_auths();
+
+        // Create the token contract
+        let token_owner = Address::generate(&env);
+        let (token, token_admin) = relink::testutils::create_token_contract(&env, &token_owner);
+
+        // Create the proxy contract
+        let proxy_id = env.register_contract(None, RelinkAutomationProxy);
+        let proxy = RelinkAutomationProxyClient::new(&env, &proxy_id);
+
+        // initialize proxy contract
+        let proxy_owner = Address::generate(&env);
+        proxy.initialize(&proxy_owner, &token.address, &10);
+
+        // add a backend to the whitelist
+        let backend = Address::generate(&env);
+        proxy.add_backend_whitelist(&backend);
+
+        // Create the target contract
+        let target_id = env.register_contract(None, TestTarget);
+        let target = TestTargetClient::new(&env, &target_id);
+        target.initialize(&proxy.address);
+
+        println!("token: {:?}", token.address);
+        println!("proxy: {:?}", proxy.address);
+        println!("target: {:?}", target.address);
+
+        Self {
+            env,
+            token,
+            token_admin,
+            token_owner,
+            proxy,
+            proxy_owner,
+            target,
+            backend,
+        }
+    }
+
+    fn random_account(&self, initial_amount: &i128) -> Address {
+        let account = Address::generate(&self.env);
+        self.token_admin.mint(&account, initial_amount);
+        account
+    }
+}
+
+#[test]
+fn register() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let token = &setup.token;
+
+    let admin = setup.random_account(&100);
+    let id1 = proxy.register_upkeep(&admin, &setup.target.address, &5, &30, &15);
+    let id2 = proxy.register_upkeep(&admin, &setup.target.address, &5, &30, &15);
+    assert_ne!(id1, id2);
+
+    assert_eq!(
+        proxy.get_upkeep(&id1),
+        Upkeep {
+            admin: admin.clone(),
+            target: setup.target.address.clone(),
+            interval: 5,
+            balance: 30,
+            max_fee: 15,
+            last_performed: setup.env.ledger().sequence(),
+        }
+    );
+    assert_eq!(token.balance(&admin), 40);
+    assert_eq!(token.balance(&proxy.address), 60);
+}
+
+#[test]
+fn perform() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let proxy = &setup.proxy;
+    let token = &setup.token;
+
+    let admin = setup.random_account(&100);
+    let id = proxy.register_upkeep(&admin, &setup.target.address, &5, &25, &15);
+
+    // not due before the interval passed
+    assert_eq!(
+        proxy.try_perform_upkeep(&setup.backend, &id),
+        Err(Ok(Error::UpkeepNotDue))
+    );
+
+    advance_ledger_sequence(env, 5);
+    proxy.perform_upkeep(&setup.backend, &id);
+    assert_eq!(setup.target.counter(), 1);
+    assert_eq!(proxy.get_upkeep(&id).balance, 15);
+    assert_eq!(token.balance(&setup.backend), 10);
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == proxy.address)
+        .last()
+        .unwrap();
+    assert_eq!(
+        vec![env, event],
+        vec![
+            env,
+            (
+                proxy.address.clone(),
+                (UPKEEP_PERFORMED, id, setup.backend.clone()).into_val(env),
+                UpkeepPerformedEvent {
+                    version: EVENT_VERSION,
+                    id,
+                    backend: setup.backend.clone(),
+                    fee: 10,
+                }
+                .into_val(env),
+            )
+        ]
+    );
+
+    // interval restarts after each execution
+    assert_eq!(
+        proxy.try_perform_upkeep(&setup.backend, &id),
+        Err(Ok(Error::UpkeepNotDue))
+    );
+    advance_ledger_sequence(env, 5);
+    proxy.perform_upkeep(&setup.backend, &id);
+    assert_eq!(setup.target.counter(), 2);
+
+    // balance is exhausted
+    advance_ledger_sequence(env, 5);
+    assert_eq!(
+        proxy.try_perform_upkeep(&setup.backend, &id),
+        Err(Ok(Error::InsufficientFunds))
+    );
+
+    // top up and continue
+    proxy.add_funds(&admin, &id, &10);
+    proxy.perform_upkeep(&setup.backend, &id);
+    assert_eq!(setup.target.counter(), 3);
+    assert_eq!(token.balance(&setup.backend), 30);
+}
+
+#[test]
+fn max_fee() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+
+    let admin = setup.random_account(&100);
+    let id = proxy.register_upkeep(&admin, &setup.target.address, &1, &100, &10);
+    advance_ledger_sequence(&setup.env, 1);
+
+    // fee raised above what the admin is willing to pay
+    proxy.set_fee(&11);
+    assert_eq!(
+        proxy.try_perform_upkeep(&setup.backend, &id),
+        Err(Ok(Error::MaxFeeExceeded))
+    );
+}
+
+#[test]
+fn unauthorized_backend() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+
+    let admin = setup.random_account(&100);
+    let id = proxy.register_upkeep(&admin, &setup.target.address, &1, &100, &10);
+    advance_ledger_sequence(&setup.env, 1);
+
+    let other = Address::generate(&setup.env);
+    assert_eq!(
+        proxy.try_perform_upkeep(&other, &id),
+        Err(Ok(Error::UnauthorizedBackend))
+    );
+}
+
+#[test]
+fn cancel() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let token = &setup.token;
+
+    let admin = setup.random_account(&100);
+    let id = proxy.register_upkeep(&admin, &setup.target.address, &1, &40, &10);
+    advance_ledger_sequence(&setup.env, 1);
+    proxy.perform_upkeep(&setup.backend, &id);
+
+    proxy.cancel_upkeep(&id);
+    assert_eq!(token.balance(&admin), 90);
+    assert_eq!(token.balance(&proxy.address), 0);
+    assert_eq!(proxy.try_get_upkeep(&id), Err(Ok(Error::UpkeepUnknown)));
+}
@@ -0,0 +1,37 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+use relink::AutomationTarget;
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Proxy,
+    Counter,
+}
+
+#[contract]
+pub struct TestTarget;
+
+#[contractimpl]
+impl TestTarget {
+    /// Initialize contract by setting the automation proxy allowed to perform upkeeps.
+    pub fn initialize(env: Env, proxy: Address) {
+        env.storage().instance().set(&DataKey::Proxy, &proxy);
+    }
+
+    /// Return the number of performed upkeeps.
+    pub fn counter(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Counter).unwrap_or(0)
+    }
+}
+
+#[contractimpl]
+impl AutomationTarget for TestTarget {
+    /// Count the number of executions.
+    fn perform(env: Env, _upkeep_id: u64) {
+        let proxy: Address = env.storage().instance().get(&DataKey::Proxy).unwrap();
+        proxy.require_auth();
+        let counter = Self::counter(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::Counter, &counter);
+    }
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use relink::events::{
+    RandomnessForwardedEvent, TargetAddedEvent, TargetRemovedEvent, EVENT_VERSION,
+    RANDOMNESS_FORWARDED, TARGET_ADDED, TARGET_REMOVED,
+};
+use relink::RequestId;
+
+pub(crate) fn target_added(env: &Env, target: Address) {
+    let topics = (TARGET_ADDED, target.clone());
+    env.events().publish(
+        topics,
+        TargetAddedEvent {
+            version: EVENT_VERSION,
+            target,
+        },
+    );
+}
+
+pub(crate) fn target_removed(env: &Env, target: Address) {
+    let topics = (TARGET_REMOVED, target.clone());
+    env.events().publish(
+        topics,
+        TargetRemovedEvent {
+            version: EVENT_VERSION,
+            target,
+        },
+    );
+}
+
+pub(crate) fn randomness_forwarded(
+    env: &Env,
+    id: RequestId,
+    target: Address,
+    random_words: Vec<BytesN<32>>,
+) {
+    let topics = (RANDOMNESS_FORWARDED, id.clone(), target.clone());
+    env.events().publish(
+        topics,
+        RandomnessForwardedEvent {
+            version: EVENT_VERSION,
+            id,
+            target,
+            random_words,
+        },
+    );
+}
@@ -0,0 +1,103 @@
+//! Shared VRF consumer for dapps without signature verification code of their own. The adapter
+//! holds one oracle configuration, requests randomness from the proxy on behalf of registered
+//! target contracts and forwards verified responses to their `RandomnessReceiver` implementation.
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+
+use relink::{
+    confirmed_owner, consumer, consumer_admin, impl_confirmed_owner, impl_vrf_consumer_admin,
+    ConfirmedOwner, Error, EthAddress, RandomnessReceiverClient, RequestId, VrfConsumerAdmin,
+    VrfDirectFundingConsumer,
+};
+
+pub mod events;
+mod storage;
+mod test;
+
+#[contract]
+pub struct RelinkConsumerAdapter;
+
+impl_confirmed_owner!(RelinkConsumerAdapter);
+impl_vrf_consumer_admin!(RelinkConsumerAdapter);
+
+#[contractimpl]
+impl RelinkConsumerAdapter {
+    /// Initialize contract by setting the owner, the proxy address and trusted oracles.
+    pub fn initialize(
+        env: Env,
+        owner: Address,
+        proxy: Address,
+        threshold: u32,
+        oracles: Vec<EthAddress>,
+    ) -> Result<(), Error> {
+        confirmed_owner::init(&env, &owner);
+        consumer::init(&env, &proxy, threshold, oracles, None)
+    }
+
+    /// Allow a target contract to request randomness through the adapter. Must be authorized by
+    /// the owner.
+    pub fn add_target(env: Env, target: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::add_target(&env, target.clone());
+        events::target_added(&env, target);
+    }
+
+    /// Stop accepting requests of a target contract. Responses to its pending requests are still
+    /// forwarded. Must be authorized by the owner.
+    pub fn remove_target(env: Env, target: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::remove_target(&env, target.clone());
+        events::target_removed(&env, target);
+    }
+
+    /// Check whether the target contract is allowed to request randomness.
+    pub fn is_target(env: Env, target: Address) -> bool {
+        storage::is_target(&env, target)
+    }
+
+    /// Request randomness for the target contract, which receives the verified words through
+    /// `receive_randomness`. Must be authorized by the target and by the origin paying the fee.
+    pub fn request_randomness(
+        env: Env,
+        target: Address,
+        origin: Address,
+        value: i128,
+        num_words: u32,
+    ) -> Result<RequestId, Error> {
+        if !storage::is_target(&env, target.clone()) {
+            return Err(Error::TargetUnknown);
+        }
+        target.require_auth();
+        let id = consumer::request_randomness(&env, origin, value, None, Some(num_words));
+        // remember the target until the response is forwarded
+        storage::set_request_target(&env, id.clone(), &target);
+        Ok(id)
+    }
+
+    /// Return the target contract of a pending request.
+    pub fn get_request_target(env: Env, id: RequestId) -> Result<Address, Error> {
+        storage::get_request_target(&env, id)
+    }
+}
+
+#[contractimpl]
+impl VrfDirectFundingConsumer for RelinkConsumerAdapter {
+    /// Verify the response and forward the random words to the target of the request.
+    fn verify_and_fulfill_randomness(
+        env: Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        // check if RequestId exists
+        let target = storage::get_request_target(&env, id.clone())?;
+        // verify signatures
+        consumer::verify_randomness(&env, &id, &random_words, &signatures)?;
+        // remove request as it should only be handled once
+        storage::remove_request_target(&env, id.clone());
+        RandomnessReceiverClient::new(&env, &target).receive_randomness(&id, &random_words);
+        events::randomness_forwarded(&env, id, target, random_words);
+        Ok(())
+    }
+}
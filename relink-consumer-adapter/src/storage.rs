@@ -0,0 +1,48 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use relink::pending_requests::REQUEST_TTL;
+use relink::{Error, RequestId};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKeyConsumerAdapter {
+    Target(Address),
+    RequestTarget(RequestId),
+}
+
+pub fn add_target(env: &Env, target: Address) {
+    let key = DataKeyConsumerAdapter::Target(target);
+    env.storage().instance().set(&key, &());
+}
+
+pub fn remove_target(env: &Env, target: Address) {
+    let key = DataKeyConsumerAdapter::Target(target);
+    env.storage().instance().remove(&key);
+}
+
+pub fn is_target(env: &Env, target: Address) -> bool {
+    let key = DataKeyConsumerAdapter::Target(target);
+    env.storage().instance().has(&key)
+}
+
+/// Remember which target a pending request was made for.
+pub fn set_request_target(env: &Env, id: RequestId, target: &Address) {
+    let key = DataKeyConsumerAdapter::RequestTarget(id);
+    env.storage().temporary().set(&key, target);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, REQUEST_TTL, REQUEST_TTL);
+}
+
+pub fn get_request_target(env: &Env, id: RequestId) -> Result<Address, Error> {
+    let key = DataKeyConsumerAdapter::RequestTarget(id);
+    env.storage()
+        .temporary()
+        .get(&key)
+        .ok_or(Error::RequestUnknown)
+}
+
+pub fn remove_request_target(env: &Env, id: RequestId) {
+    let key = DataKeyConsumerAdapter::RequestTarget(id);
+    env.storage().temporary().remove(&key);
+}
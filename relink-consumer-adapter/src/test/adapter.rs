@@ -0,0 +1,165 @@
+extern crate std;
+
+use std::println;
+
+use relink::testutils::{TestOracle, TestOracleGenerator};
+use relink::{Error, RequestId};
+use relink_vrf_direct_funding_proxy::{
+    RelinkVrfDirectFundingProxy, RelinkVrfDirectFundingProxyClient,
+};
+use soroban_sdk::testutils::Address as AddressTestTrait;
+use soroban_sdk::{token, vec, Address, BytesN, Env, Vec};
+
+use crate::test::test_target::{TestTarget, TestTargetClient};
+use crate::{RelinkConsumerAdapter, RelinkConsumerAdapterClient};
+
+struct Setup<'a> {
+    env: Env,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    proxy: RelinkVrfDirectFundingProxyClient<'a>,
+    adapter: RelinkConsumerAdapterClient<'a>,
+    target: TestTargetClient<'a>,
+    backend: Address,
+    oracles: std::vec::Vec<TestOracle>,
+}
+
+impl Setup<'_> {
+    fn new() -> Self {
+        let env = Env::default();
+        // the user authorizes the fee transfer within the call of the target contract
+        env.mock_all_auths_allowing_non_root_auth();
+
+        // Create the token contract
+        let token_owner = Address::generate(&env);
+        let (token, token_admin) = relink::testutils::create_token_contract(&env, &token_owner);
+
+        // Create and initialize the proxy contract
+        let proxy_id = env.register_contract(None, RelinkVrfDirectFundingProxy);
+        let proxy = RelinkVrfDirectFundingProxyClient::new(&env, &proxy_id);
+        proxy.initialize(&Address::generate(&env), &token.address, &10);
+        let backend = Address::generate(&env);
+        proxy.add_backend_whitelist(&backend);
+
+        // Create and initialize the adapter contract with two of three oracles
+        let oracles = TestOracleGenerator::new().generate_sorted(&env, 3);
+        let mut oracle_addresses = Vec::new(&env);
+        for oracle in oracles.iter() {
+            oracle_addresses.push_back(oracle.address(&env));
+        }
+        let adapter_id = env.register_contract(None, RelinkConsumerAdapter);
+        let adapter = RelinkConsumerAdapterClient::new(&env, &adapter_id);
+        adapter.initialize(
+            &Address::generate(&env),
+            &proxy.address,
+            &2,
+            &oracle_addresses,
+        );
+
+        // Create the target contract and register it with the adapter
+        let target_id = env.register_contract(None, TestTarget);
+        let target = TestTargetClient::new(&env, &target_id);
+        target.initialize(&adapter.address);
+        adapter.add_target(&target.address);
+
+        println!("proxy: {:?}", proxy.address);
+        println!("adapter: {:?}", adapter.address);
+        println!("target: {:?}", target.address);
+
+        Self {
+            env,
+            token,
+            token_admin,
+            proxy,
+            adapter,
+            target,
+            backend,
+            oracles,
+        }
+    }
+
+    fn random_account(&self, amount: &i128) -> Address {
+        let account = Address::generate(&self.env);
+        self.token_admin.mint(&account, amount);
+        account
+    }
+
+    fn sign(&self, id: &RequestId, random_words: &Vec<BytesN<32>>) -> Vec<(BytesN<64>, u32)> {
+        let env = &self.env;
+        vec![
+            env,
+            self.oracles[0].sign(env, &self.adapter.address, id, random_words),
+            self.oracles[2].sign(env, &self.adapter.address, id, random_words),
+        ]
+    }
+}
+
+#[test]
+fn forward_randomness() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let adapter = &setup.adapter;
+
+    let user = setup.random_account(&50);
+    let id = setup.target.request(&user, &10);
+    assert_eq!(setup.token.balance(&setup.proxy.address), 10);
+    assert_eq!(adapter.get_request_target(&id), setup.target.address);
+
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let signatures = setup.sign(&id, &random_words);
+    setup
+        .proxy
+        .callback_with_randomness(&setup.backend, &id, &random_words, &signatures);
+    assert_eq!(
+        setup.target.received(),
+        vec![env, (id.clone(), random_words.clone())]
+    );
+    assert_eq!(setup.proxy.stats().fulfilled, 1);
+    // the request is only forwarded once
+    assert_eq!(
+        adapter.try_get_request_target(&id),
+        Err(Ok(Error::RequestUnknown))
+    );
+}
+
+#[test]
+fn unknown_target() {
+    let setup = Setup::new();
+    let adapter = &setup.adapter;
+
+    let user = setup.random_account(&50);
+    adapter.remove_target(&setup.target.address);
+    assert!(!adapter.is_target(&setup.target.address));
+    assert_eq!(
+        adapter.try_request_randomness(&setup.target.address, &user, &10, &1),
+        Err(Ok(Error::TargetUnknown))
+    );
+    assert_eq!(setup.token.balance(&user), 50);
+}
+
+#[test]
+fn tampered_randomness() {
+    let setup = Setup::new();
+    let env = &setup.env;
+
+    let user = setup.random_account(&50);
+    let id = setup.target.request(&user, &10);
+    let signatures = setup.sign(&id, &vec![env, BytesN::from_array(env, &[7; 32])]);
+    let tampered = vec![env, BytesN::from_array(env, &[8; 32])];
+    assert_eq!(
+        setup
+            .adapter
+            .try_verify_and_fulfill_randomness(&id, &tampered, &signatures),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    // through the proxy the failed callback reverts and leaves the request pending
+    assert_eq!(
+        setup
+            .proxy
+            .try_callback_with_randomness(&setup.backend, &id, &tampered, &signatures),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    assert_eq!(setup.proxy.stats().failed_callbacks, 0);
+    assert!(setup.target.received().is_empty());
+    assert_eq!(setup.adapter.get_request_target(&id), setup.target.address);
+}
@@ -0,0 +1,4 @@
+#![cfg(test)]
+
+mod adapter;
+mod test_target;
@@ -0,0 +1,55 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use relink::{RandomnessReceiver, RequestId};
+
+use crate::RelinkConsumerAdapterClient;
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Adapter,
+    Received,
+}
+
+#[contract]
+pub struct TestTarget;
+
+#[contractimpl]
+impl TestTarget {
+    /// Initialize contract by setting the adapter contract allowed to deliver randomness.
+    pub fn initialize(env: Env, adapter: Address) {
+        env.storage().instance().set(&DataKey::Adapter, &adapter);
+    }
+
+    /// Request a random word through the adapter, paid by the origin.
+    pub fn request(env: Env, origin: Address, value: i128) -> RequestId {
+        let adapter: Address = env.storage().instance().get(&DataKey::Adapter).unwrap();
+        RelinkConsumerAdapterClient::new(&env, &adapter).request_randomness(
+            &env.current_contract_address(),
+            &origin,
+            &value,
+            &1,
+        )
+    }
+
+    /// Return all random words received so far.
+    pub fn received(env: Env) -> Vec<(RequestId, Vec<BytesN<32>>)> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Received)
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+#[contractimpl]
+impl RandomnessReceiver for TestTarget {
+    /// Record the random words.
+    fn receive_randomness(env: Env, id: RequestId, random_words: Vec<BytesN<32>>) {
+        // only words forwarded by the adapter were verified
+        let adapter: Address = env.storage().instance().get(&DataKey::Adapter).unwrap();
+        adapter.require_auth();
+        let mut received = Self::received(env.clone());
+        received.push_back((id, random_words));
+        env.storage().instance().set(&DataKey::Received, &received);
+    }
+}
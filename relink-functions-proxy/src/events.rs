@@ -0,0 +1,89 @@
+use soroban_sdk::{Address, Bytes, Env};
+
+use relink::events::{
+    FeeSetEvent, FunctionsRequestedEvent, FunctionsRespondedEvent, WhitelistAddedEvent,
+    WhitelistRemovedEvent, WithdrawnEvent, EVENT_VERSION, FEE_SET, FUNCTIONS_REQUESTED,
+    FUNCTIONS_RESPONDED, WHITELIST_ADDED, WHITELIST_REMOVED, WITHDRAWN,
+};
+use relink::RequestId;
+
+pub(crate) fn request_sent(
+    env: &Env,
+    origin: Address,
+    dapp: Address,
+    nonce: u128,
+    id: RequestId,
+    payload: Bytes,
+) {
+    let topics = (FUNCTIONS_REQUESTED, origin.clone(), dapp.clone());
+    env.events().publish(
+        topics,
+        FunctionsRequestedEvent {
+            version: EVENT_VERSION,
+            origin,
+            dapp,
+            nonce,
+            id,
+            payload,
+        },
+    );
+}
+
+pub(crate) fn response_provided(env: &Env, id: RequestId, response: Bytes, err: Bytes) {
+    let topics = (FUNCTIONS_RESPONDED, id.clone());
+    env.events().publish(
+        topics,
+        FunctionsRespondedEvent {
+            version: EVENT_VERSION,
+            id,
+            response,
+            err,
+        },
+    );
+}
+
+pub(crate) fn fee_set(env: &Env, fee: i128) {
+    let topics = (FEE_SET,);
+    env.events().publish(
+        topics,
+        FeeSetEvent {
+            version: EVENT_VERSION,
+            fee,
+        },
+    );
+}
+
+pub(crate) fn whitelist_address_added(env: &Env, address: Address) {
+    let topics = (WHITELIST_ADDED, address.clone());
+    env.events().publish(
+        topics,
+        WhitelistAddedEvent {
+            version: EVENT_VERSION,
+            address,
+        },
+    );
+}
+
+pub(crate) fn whitelist_address_removed(env: &Env, address: Address) {
+    let topics = (WHITELIST_REMOVED, address.clone());
+    env.events().publish(
+        topics,
+        WhitelistRemovedEvent {
+            version: EVENT_VERSION,
+            address,
+        },
+    );
+}
+
+pub(crate) fn withdrawn(env: &Env, token: Address, to: Address, amount: i128) {
+    let topics = (WITHDRAWN, token.clone());
+    env.events().publish(
+        topics,
+        WithdrawnEvent {
+            version: EVENT_VERSION,
+            token,
+            to,
+            amount,
+        },
+    );
+}
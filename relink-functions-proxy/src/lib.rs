@@ -0,0 +1,128 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, token, Address, Bytes, BytesN, Env, Vec};
+
+use relink::{
+    confirmed_owner, impl_confirmed_owner, ConfirmedOwner, Error, FunctionsConsumerClient,
+    FunctionsProxy, RequestId,
+};
+
+pub mod events;
+mod storage;
+mod test;
+
+#[contract]
+pub struct RelinkFunctionsProxy;
+
+impl_confirmed_owner!(RelinkFunctionsProxy);
+
+#[contractimpl]
+impl RelinkFunctionsProxy {
+    /// Initialize contract by setting the owner address and token to use.
+    pub fn initialize(env: Env, owner: Address, token: Address, fee: i128) {
+        confirmed_owner::init(&env, &owner);
+        storage::set_token(&env, &token);
+        storage::set_fee(&env, &fee);
+    }
+
+    /// Add an address to the backend whitelist.
+    pub fn add_backend_whitelist(env: Env, address: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::add_whitelist(&env, address.clone());
+        events::whitelist_address_added(&env, address.clone());
+    }
+
+    /// Remove an address from the backend whitelist.
+    pub fn remove_backend_whitelist(env: Env, address: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::remove_whitelist(&env, address.clone());
+        events::whitelist_address_removed(&env, address.clone());
+    }
+
+    /// Update the fee for calling send_request.
+    pub fn set_fee(env: Env, fee: i128) {
+        confirmed_owner::require_owner(&env);
+        storage::set_fee(&env, &fee);
+        events::fee_set(&env, fee);
+    }
+
+    /// Get the current fee for calling send_request.
+    pub fn get_fee(env: Env) -> i128 {
+        storage::get_fee(&env)
+    }
+
+    /// Withdraw all tokens from the contract to the owner.
+    pub fn withdraw(env: Env, token: Address) {
+        confirmed_owner::require_owner(&env);
+        let client = token::Client::new(&env, &token);
+        let this_contract = env.current_contract_address();
+        // withdraw all tokens to the owner
+        let balance = client.balance(&this_contract);
+        let owner = confirmed_owner::owner(&env);
+        client.transfer(&this_contract, &owner, &balance);
+        events::withdrawn(&env, token, owner, balance);
+    }
+}
+
+#[contractimpl]
+impl FunctionsProxy for RelinkFunctionsProxy {
+    /// Generate a new request for off-chain computation with an opaque payload.
+    fn send_request(
+        env: Env,
+        origin: Address,
+        value: i128,
+        dapp: Address,
+        payload: Bytes,
+    ) -> Result<RequestId, Error> {
+        // assert minimum fee
+        if value < storage::get_fee(&env) {
+            return Err(Error::InsufficientFee);
+        }
+        // require authorization by sender
+        origin.require_auth();
+
+        // transfer fee to this contract
+        let token_id = storage::get_token(&env);
+        let client = token::Client::new(&env, &token_id);
+        client.transfer(&origin, &env.current_contract_address(), &value);
+
+        // generate unique request id
+        let nonce = storage::get_and_increment_nonce(&env);
+        let id = RequestId::new(&env, &origin, &dapp, &env.current_contract_address(), nonce);
+
+        // store a mapping of request-id => dapp address
+        storage::set_request_dapp(&env, id.clone(), &dapp);
+
+        // emit event containing the payload for the backends to pick up
+        events::request_sent(&env, origin, dapp, nonce, id.clone(), payload);
+
+        Ok(id)
+    }
+
+    /// Entry point for computation results coming from a backend.
+    fn callback_with_response(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        response: Bytes,
+        err: Bytes,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        // only backends on the whitelist are allowed to call this
+        backend.require_auth();
+        storage::is_whitelisted(&env, backend)?;
+        // read and then remove originating dapp address, error if not found
+        let dapp = storage::get_request_dapp(&env, id.clone())?;
+        storage::remove_request_dapp(&env, id.clone());
+        // callback to dapp contract with provided response, a rejected response is reverted
+        let dapp_client = FunctionsConsumerClient::new(&env, &dapp);
+        match dapp_client.try_verify_and_fulfill_response(&id, &response, &err, &signatures) {
+            Ok(Ok(())) => {}
+            Err(Ok(error)) => return Err(error),
+            _ => panic!("consumer callback failed"),
+        }
+        // emit event containing the provided response
+        events::response_provided(&env, id, response, err);
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+extern crate std;
+
+use std::println;
+
+use relink::testutils::TestOracleGenerator;
+use soroban_sdk::testutils::{
+    Address as AddressTestTrait, AuthorizedFunction, AuthorizedInvocation,
+};
+use soroban_sdk::{bytes, token, vec, Address, Bytes, Env, IntoVal, Symbol};
+
+use crate::test::test_consumer::{TestConsumer, TestConsumerClient};
+use crate::{RelinkFunctionsProxy, RelinkFunctionsProxyClient};
+
+#[allow(dead_code)]
+struct Setup<'a> {
+    env: Env,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    token_owner: Address,
+    proxy: RelinkFunctionsProxyClient<'a>,
+    consumer: TestConsumerClient<'a>,
+}
+
+impl Setup<'_> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        // Create the token contract
+        let token_owner = Address::generate(&env);
+        let (token, token_admin) = relink::testutils::create_token_contract(&env, &token_owner);
+
+        // Create the proxy contract
+        let proxy_id = env.register_contract(None, RelinkFunctionsProxy);
+        let proxy = RelinkFunctionsProxyClient::new(&env, &proxy_id);
+
+        // initialize proxy contract
+        let proxy_owner = Address::generate(&env);
+        proxy.initialize(&proxy_owner, &token.address, &10);
+
+        // Create the consumer contract
+        let consumer_id = env.register_contract(None, TestConsumer);
+        let consumer = TestConsumerClient::new(&env, &consumer_id);
+
+        println!("token: {:?}", token.address);
+        println!("token_owner: {:?}", token_owner);
+        println!("proxy: {:?}", proxy.address);
+        println!("proxy_owner: {:?}", proxy_owner);
+        println!("consumer: {:?}", consumer.address);
+
+        Self {
+            env,
+            token,
+            token_admin,
+            token_owner,
+            proxy,
+            consumer,
+        }
+    }
+
+    fn fund_account(&self, account: &Address, amount: &i128) {
+        // Mint some tokens to work with
+        self.token_admin.mint(account, amount);
+    }
+
+    fn random_account(&self, initial_amount: &i128) -> Address {
+        let account = Address::generate(&self.env);
+        self.fund_account(&account, initial_amount);
+        account
+    }
+}
+
+#[test]
+fn send_request() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // generate some key pairs for oracles
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 3);
+    let oracle1 = &oracles[0];
+    let oracle2 = &oracles[1];
+    let oracle3 = &oracles[2];
+
+    // initialize consumer contract
+    let oracles_pub_keys = vec![
+        env,
+        oracle1.address(env),
+        oracle2.address(env),
+        oracle3.address(env),
+    ];
+    consumer.initialize(&proxy.address, &2, &oracles_pub_keys);
+
+    // initiate a request
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_request(&user, &10, &bytes!(env, 0xcafe));
+    println!("request id: {}", id);
+
+    // add a backend to the whitelist
+    let backend = setup.random_account(&0);
+    proxy.add_backend_whitelist(&backend);
+
+    // produce a response
+    let response = bytes!(env, 0x000102030405060708090a0b0c0d0e0f);
+    let err = Bytes::new(env);
+    // generate oracle signatures
+    let signatures = vec![
+        env,
+        oracle1.sign_response(env, &consumer.address, &id, &response, &err),
+        oracle3.sign_response(env, &consumer.address, &id, &response, &err),
+    ];
+
+    // execute the callback with the response
+    proxy.callback_with_response(&backend, &id, &response, &err, &signatures);
+
+    // verify that the backend's signature was checked
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            // Address for which authorization check is performed
+            backend.clone(),
+            // Invocation tree that needs to be authorized
+            AuthorizedInvocation {
+                // Function that is authorized. Can be a contract function or a host function that requires authorization.
+                function: AuthorizedFunction::Contract((
+                    // Address of the called contract
+                    proxy.address.clone(),
+                    // Name of the called function
+                    Symbol::new(env, "callback_with_response"),
+                    // Arguments used to call `callback_with_response` (converted to the env-managed vector via `into_val`)
+                    (
+                        backend.clone(),
+                        id.clone(),
+                        response.clone(),
+                        err.clone(),
+                        signatures.clone()
+                    )
+                        .into_val(env),
+                )),
+                // The contract doesn't call any other contracts that require authorization,
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    // the consumer received the response
+    assert_eq!(consumer.get_response(&id), (response.clone(), err.clone()));
+
+    // the request can only be fulfilled once
+    let result = proxy.try_callback_with_response(&backend, &id, &response, &err, &signatures);
+    assert_eq!(result, Err(Ok(relink::Error::RequestUnknown)));
+}
+
+#[test]
+fn tampered_response() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // generate some key pairs for oracles
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 2);
+    let oracle1 = &oracles[0];
+    let oracle2 = &oracles[1];
+    consumer.initialize(
+        &proxy.address,
+        &2,
+        &vec![env, oracle1.address(env), oracle2.address(env)],
+    );
+
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_request(&user, &10, &bytes!(env, 0xcafe));
+    let backend = setup.random_account(&0);
+    proxy.add_backend_whitelist(&backend);
+
+    // oracles sign an error, backend relays it as a successful response
+    let err = bytes!(env, 0xdead);
+    let signatures = vec![
+        env,
+        oracle1.sign_response(env, &consumer.address, &id, &Bytes::new(env), &err),
+        oracle2.sign_response(env, &consumer.address, &id, &Bytes::new(env), &err),
+    ];
+    let result =
+        proxy.try_callback_with_response(&backend, &id, &err, &Bytes::new(env), &signatures);
+    assert_eq!(result, Err(Ok(relink::Error::UnauthorizedOracleSignatures)));
+}
@@ -0,0 +1,5 @@
+#![cfg(test)]
+
+mod consumer;
+mod proxy;
+mod test_consumer;
@@ -0,0 +1,207 @@
+extern crate std;
+
+use std::println;
+
+use soroban_sdk::{
+    bytes,
+    testutils::{Address as AddressTestTrait, Events},
+    token, vec, Address, Env, IntoVal, Vec,
+};
+
+use relink::events::{
+    FunctionsRequestedEvent, WithdrawnEvent, EVENT_VERSION, FUNCTIONS_REQUESTED, WITHDRAWN,
+};
+use relink::RequestId;
+
+use crate::{RelinkFunctionsProxy, RelinkFunctionsProxyClient};
+
+#[allow(dead_code)]
+struct Setup<'a> {
+    env: Env,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    token_owner: Address,
+    proxy: RelinkFunctionsProxyClient<'a>,
+    proxy_owner: Address,
+}
+
+impl Setup<'_> {
+    fn new() -> Self {
+        let env: Env = Env::default();
+        env.mock_all_auths();
+
+        // Create the token contract
+        let token_owner = Address::generate(&env);
+        let (token, token_admin) = relink::testutils::create_token_contract(&env, &token_owner);
+
+        // Create the proxy contract
+        let proxy_id = env.register_contract(None, RelinkFunctionsProxy);
+        let proxy = RelinkFunctionsProxyClient::new(&env, &proxy_id);
+
+        // initialize proxy contract
+        let proxy_owner = Address::generate(&env);
+        proxy.initialize(&proxy_owner, &token.address, &10);
+
+        println!("token: {:?}", token.address);
+        println!("token_owner: {:?}", token_owner);
+        println!("proxy: {:?}", proxy.address);
+        println!("proxy_owner: {:?}", proxy_owner);
+
+        Self {
+            env,
+            token,
+            token_admin,
+            token_owner,
+            proxy,
+            proxy_owner,
+        }
+    }
+
+    fn fund_account(&self, account: &Address, amount: &i128) {
+        // Mint some tokens to work with
+        self.token_admin.mint(account, amount);
+    }
+
+    fn random_account(&self, initial_amount: &i128) -> Address {
+        let account = Address::generate(&self.env);
+        self.fund_account(&account, initial_amount);
+        account
+    }
+}
+
+#[test]
+fn init() {
+    let setup = Setup::new();
+    assert_eq!(setup.proxy.owner(), setup.proxy_owner);
+    assert_eq!(setup.proxy.get_fee(), 10);
+}
+
+#[test]
+#[should_panic(expected = "already initialized")]
+fn reinit() {
+    let setup = Setup::new();
+    setup
+        .proxy
+        .initialize(&setup.proxy_owner, &setup.token.address, &123);
+}
+
+#[test]
+fn request_event() {
+    let setup = Setup::new();
+    let env = &setup.env;
+
+    let origin = setup.random_account(&50);
+    let dapp = setup.random_account(&0);
+    let payload = bytes!(env, 0x0102030405);
+    let request_id = setup.proxy.send_request(&origin, &10, &dapp, &payload);
+    println!("request id: {}", request_id);
+
+    let mut proxy_events = Vec::new(env);
+
+    // there are SAC events emitted also, filter those away, not asserting that aspect
+    env.events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == setup.proxy.address)
+        .for_each(|event| proxy_events.push_back(event));
+
+    let expected_nonce = 0_u128;
+    let expected_request_id =
+        RequestId::new(env, &origin, &dapp, &setup.proxy.address, expected_nonce);
+
+    assert_eq!(request_id, expected_request_id);
+
+    assert_eq!(
+        proxy_events,
+        vec![
+            env,
+            (
+                setup.proxy.address.clone(),
+                (FUNCTIONS_REQUESTED, origin.clone(), dapp.clone()).into_val(env),
+                FunctionsRequestedEvent {
+                    version: EVENT_VERSION,
+                    origin,
+                    dapp,
+                    nonce: expected_nonce,
+                    id: expected_request_id,
+                    payload,
+                }
+                .into_val(env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn balance() {
+    let setup = Setup::new();
+    let token = &setup.token;
+    let proxy = &setup.proxy;
+    let env = &setup.env;
+
+    let origin = setup.random_account(&50);
+    let dapp = setup.random_account(&0);
+
+    proxy.send_request(&origin, &10, &dapp, &bytes!(env, 0x01));
+    proxy.send_request(&origin, &15, &dapp, &bytes!(env, 0x02));
+
+    assert_eq!(token.balance(&origin), 25);
+    assert_eq!(token.balance(&proxy.address), 25);
+
+    // withdraw all tokens to the owner
+    proxy.withdraw(&token.address);
+
+    assert_eq!(token.balance(&proxy.owner()), 25);
+    assert_eq!(token.balance(&proxy.address), 0);
+}
+
+#[test]
+fn withdraw_event() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let proxy = &setup.proxy;
+
+    let origin = setup.random_account(&50);
+    let dapp = setup.random_account(&0);
+    proxy.send_request(&origin, &10, &dapp, &bytes!(env, 0x01));
+    proxy.withdraw(&setup.token.address);
+
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == proxy.address)
+        .last()
+        .unwrap();
+    assert_eq!(
+        vec![env, event],
+        vec![
+            env,
+            (
+                proxy.address.clone(),
+                (WITHDRAWN, setup.token.address.clone()).into_val(env),
+                WithdrawnEvent {
+                    version: EVENT_VERSION,
+                    token: setup.token.address.clone(),
+                    to: setup.proxy_owner.clone(),
+                    amount: 10,
+                }
+                .into_val(env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn insufficient_fee() {
+    let setup = Setup::new();
+    let env = &setup.env;
+
+    let origin = setup.random_account(&50);
+    let dapp = setup.random_account(&0);
+
+    let result = setup
+        .proxy
+        .try_send_request(&origin, &9, &dapp, &bytes!(env, 0x01));
+    assert_eq!(result, Err(Ok(relink::Error::InsufficientFee)));
+}
@@ -0,0 +1,91 @@
+extern crate std;
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Vec};
+
+use relink::{consumer, functions, Error, EthAddress, FunctionsConsumer, RequestId};
+
+const REQUEST_BUMP_AMOUNT: u32 = 34560; // 2 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Request(RequestId),
+    Response(RequestId),
+}
+
+pub fn add_request_id(env: &Env, id: RequestId) {
+    let key = DataKey::Request(id);
+    env.storage().temporary().set(&key, &());
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, REQUEST_BUMP_AMOUNT, REQUEST_BUMP_AMOUNT);
+}
+
+pub fn has_request_id(env: &Env, id: RequestId) -> Result<(), Error> {
+    let key = DataKey::Request(id);
+    env.storage()
+        .temporary()
+        .get(&key)
+        .ok_or(Error::RequestUnknown)
+}
+
+pub fn remove_request_id(env: &Env, id: RequestId) {
+    let key = DataKey::Request(id);
+    env.storage().temporary().remove(&key);
+}
+
+#[contract]
+pub struct TestConsumer;
+
+#[contractimpl]
+impl TestConsumer {
+    /// Initialize contract by setting the proxy address and trusted oracles.
+    pub fn initialize(
+        env: Env,
+        proxy: Address,
+        threshold: u32,
+        oracles: Vec<EthAddress>,
+    ) -> Result<(), Error> {
+        consumer::init(&env, &proxy, threshold, oracles, None)
+    }
+
+    /// Initiate a request for off-chain computation.
+    pub fn initiate_request(env: Env, origin: Address, value: i128, payload: Bytes) -> RequestId {
+        let id = functions::send_request(&env, origin, value, payload);
+        // store the request id until the response is processed
+        add_request_id(&env, id.clone());
+        id
+    }
+
+    /// Return the response stored for a fulfilled request.
+    pub fn get_response(env: Env, id: RequestId) -> (Bytes, Bytes) {
+        env.storage()
+            .instance()
+            .get(&DataKey::Response(id))
+            .unwrap()
+    }
+}
+
+#[contractimpl]
+impl FunctionsConsumer for TestConsumer {
+    /// Process the response to a computation request.
+    fn verify_and_fulfill_response(
+        env: Env,
+        id: RequestId,
+        response: Bytes,
+        err: Bytes,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        // check if RequestId exists
+        has_request_id(&env, id.clone())?;
+        // verify signatures
+        functions::verify_response(&env, &id, &response, &err, &signatures)?;
+        // remove request as it should only be handled once
+        remove_request_id(&env, id.clone());
+        // keep the response around for inspection
+        env.storage()
+            .instance()
+            .set(&DataKey::Response(id), &(response, err));
+        Ok(())
+    }
+}
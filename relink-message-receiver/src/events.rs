@@ -0,0 +1,65 @@
+use soroban_sdk::{Address, Env};
+
+use relink::events::{
+    MessageReceivedEvent, MessageSkippedEvent, ReceiverRemovedEvent, ReceiverSetEvent,
+    EVENT_VERSION, MESSAGE_RECEIVED, MESSAGE_SKIPPED, RECEIVER_REMOVED, RECEIVER_SET,
+};
+use relink::EthAddress;
+
+pub(crate) fn message_received(
+    env: &Env,
+    source_chain: u64,
+    sender: EthAddress,
+    nonce: u64,
+    receiver: Address,
+) {
+    let topics = (MESSAGE_RECEIVED, source_chain, sender.clone());
+    env.events().publish(
+        topics,
+        MessageReceivedEvent {
+            version: EVENT_VERSION,
+            source_chain,
+            sender,
+            nonce,
+            receiver,
+        },
+    );
+}
+
+pub(crate) fn message_skipped(env: &Env, source_chain: u64, sender: EthAddress, nonce: u64) {
+    let topics = (MESSAGE_SKIPPED, source_chain, sender.clone());
+    env.events().publish(
+        topics,
+        MessageSkippedEvent {
+            version: EVENT_VERSION,
+            source_chain,
+            sender,
+            nonce,
+        },
+    );
+}
+
+pub(crate) fn receiver_set(env: &Env, source_chain: u64, sender: EthAddress, receiver: Address) {
+    let topics = (RECEIVER_SET, source_chain, sender.clone());
+    env.events().publish(
+        topics,
+        ReceiverSetEvent {
+            version: EVENT_VERSION,
+            source_chain,
+            sender,
+            receiver,
+        },
+    );
+}
+
+pub(crate) fn receiver_removed(env: &Env, source_chain: u64, sender: EthAddress) {
+    let topics = (RECEIVER_REMOVED, source_chain, sender.clone());
+    env.events().publish(
+        topics,
+        ReceiverRemovedEvent {
+            version: EVENT_VERSION,
+            source_chain,
+            sender,
+        },
+    );
+}
@@ -0,0 +1,94 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Vec};
+
+use relink::{
+    confirmed_owner, consumer, consumer_admin, impl_confirmed_owner, impl_vrf_consumer_admin,
+    messaging, ConfirmedOwner, Error, EthAddress, MessageReceiverClient, VrfConsumerAdmin,
+};
+
+pub mod events;
+mod storage;
+mod test;
+
+#[contract]
+pub struct RelinkMessageReceiver;
+
+impl_confirmed_owner!(RelinkMessageReceiver);
+impl_vrf_consumer_admin!(RelinkMessageReceiver);
+
+#[contractimpl]
+impl RelinkMessageReceiver {
+    /// Initialize contract by setting the owner address and trusted oracles.
+    pub fn initialize(
+        env: Env,
+        owner: Address,
+        threshold: u32,
+        oracles: Vec<EthAddress>,
+    ) -> Result<(), Error> {
+        confirmed_owner::init(&env, &owner);
+        consumer::init_oracles(&env, threshold, oracles, None)
+    }
+
+    /// Route messages from a sender contract on the source chain to a Soroban receiver contract.
+    pub fn set_receiver(env: Env, source_chain: u64, sender: EthAddress, receiver: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::set_receiver(&env, source_chain, sender.clone(), &receiver);
+        events::receiver_set(&env, source_chain, sender, receiver);
+    }
+
+    /// Stop routing messages from a sender contract on the source chain.
+    pub fn remove_receiver(env: Env, source_chain: u64, sender: EthAddress) {
+        confirmed_owner::require_owner(&env);
+        storage::remove_receiver(&env, source_chain, sender.clone());
+        events::receiver_removed(&env, source_chain, sender);
+    }
+
+    /// Get the Soroban receiver contract for a sender contract on the source chain.
+    pub fn get_receiver(env: Env, source_chain: u64, sender: EthAddress) -> Result<Address, Error> {
+        storage::get_receiver(&env, source_chain, sender)
+    }
+
+    /// Get the nonce expected for the next message from a sender contract on the source chain.
+    pub fn get_nonce(env: Env, source_chain: u64, sender: EthAddress) -> u64 {
+        storage::get_nonce(&env, source_chain, sender)
+    }
+
+    /// Give up on the message with the expected nonce from a sender contract, so that the
+    /// following messages can be delivered. For messages that can't be delivered, for example
+    /// because their receiver rejects them. Must be authorized by the owner.
+    pub fn skip_message(
+        env: Env,
+        source_chain: u64,
+        sender: EthAddress,
+        nonce: u64,
+    ) -> Result<(), Error> {
+        confirmed_owner::require_owner(&env);
+        storage::use_nonce(&env, source_chain, sender.clone(), nonce)?;
+        events::message_skipped(&env, source_chain, sender, nonce);
+        Ok(())
+    }
+
+    /// Entry point for messages relayed by the oracles. Anyone may submit a message, its
+    /// authenticity is established by the oracle signatures.
+    pub fn receive_message(
+        env: Env,
+        source_chain: u64,
+        sender: EthAddress,
+        nonce: u64,
+        payload: Bytes,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        // verify oracle signatures
+        messaging::verify_message(&env, source_chain, &sender, nonce, &payload, &signatures)?;
+        // enforce ordering per sender contract, this also prevents replays
+        storage::use_nonce(&env, source_chain, sender.clone(), nonce)?;
+        // dispatch to the registered receiver contract
+        let receiver = storage::get_receiver(&env, source_chain, sender.clone())?;
+        let receiver_client = MessageReceiverClient::new(&env, &receiver);
+        receiver_client.handle_message(&source_chain, &sender, &payload);
+        // emit event
+        events::message_received(&env, source_chain, sender, nonce, receiver);
+        Ok(())
+    }
+}
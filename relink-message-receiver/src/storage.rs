@@ -0,0 +1,53 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use relink::{Error, EthAddress};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKeyMessageReceiver {
+    Nonce(u64, EthAddress),
+    Receiver(u64, EthAddress),
+}
+
+pub fn get_nonce(env: &Env, source_chain: u64, sender: EthAddress) -> u64 {
+    env.storage()
+        .instance()
+        .get::<_, u64>(&DataKeyMessageReceiver::Nonce(source_chain, sender))
+        .unwrap_or(0)
+}
+
+/// Consume the next nonce of the sender contract, its messages have to arrive in order.
+pub fn use_nonce(
+    env: &Env,
+    source_chain: u64,
+    sender: EthAddress,
+    nonce: u64,
+) -> Result<(), Error> {
+    let expected = get_nonce(env, source_chain, sender.clone());
+    if nonce != expected {
+        return Err(Error::InvalidNonce);
+    }
+    env.storage().instance().set::<_, u64>(
+        &DataKeyMessageReceiver::Nonce(source_chain, sender),
+        &expected.checked_add(1).unwrap(),
+    );
+    Ok(())
+}
+
+pub fn set_receiver(env: &Env, source_chain: u64, sender: EthAddress, receiver: &Address) {
+    let key = DataKeyMessageReceiver::Receiver(source_chain, sender);
+    env.storage().instance().set(&key, receiver);
+}
+
+pub fn remove_receiver(env: &Env, source_chain: u64, sender: EthAddress) {
+    let key = DataKeyMessageReceiver::Receiver(source_chain, sender);
+    env.storage().instance().remove(&key);
+}
+
+pub fn get_receiver(env: &Env, source_chain: u64, sender: EthAddress) -> Result<Address, Error> {
+    let key = DataKeyMessageReceiver::Receiver(source_chain, sender);
+    env.storage()
+        .instance()
+        .get(&key)
+        .ok_or(Error::ReceiverUnknown)
+}
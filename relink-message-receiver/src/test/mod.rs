@@ -0,0 +1,4 @@
+#![cfg(test)]
+
+mod receiver;
+mod test_receiver;
@@ -0,0 +1,369 @@
+extern crate std;
+
+use std::println;
+
+use relink::events::{
+    MessageReceivedEvent, MessageSkippedEvent, EVENT_VERSION, MESSAGE_RECEIVED, MESSAGE_SKIPPED,
+};
+use relink::testutils::{TestOracle, TestOracleGenerator};
+use relink::{Error, EthAddress};
+use soroban_sdk::testutils::{
+    Address as AddressTestTrait, AuthorizedFunction, AuthorizedInvocation, Events,
+};
+use soroban_sdk::{bytes, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::test::test_receiver::{TestReceiver, TestReceiverClient};
+use crate::{RelinkMessageReceiver, RelinkMessageReceiverClient};
+
+const SOURCE_CHAIN: u64 = 11155111;
+
+struct Setup<'a> {
+    env: Env,
+    owner: Address,
+    router: RelinkMessageReceiverClient<'a>,
+    receiver: TestReceiverClient<'a>,
+    oracles: std::vec::Vec<TestOracle>,
+    sender: EthAddress,
+}
+
+impl Setup<'_> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        // generate some key pairs for oracles
+        let oracles = TestOracleGenerator::new().generate_sorted(&env, 3);
+        let mut oracle_addresses = Vec::new(&env);
+        for oracle in oracles.iter() {
+            oracle_addresses.push_back(oracle.address(&env));
+        }
+
+        // Create the message receiver contract
+        let router_id = env.register_contract(None, RelinkMessageReceiver);
+        let router = RelinkMessageReceiverClient::new(&env, &router_id);
+        let owner = Address::generate(&env);
+        router.initialize(&owner, &2, &oracle_addresses);
+
+        // Create the receiver contract
+        let receiver_id = env.register_contract(None, TestReceiver);
+        let receiver = TestReceiverClient::new(&env, &receiver_id);
+        receiver.initialize(&router.address);
+
+        // route messages from the sender contract to the receiver
+        let sender = EthAddress::from_bytes(BytesN::from_array(&env, &[0xab; 20]));
+        router.set_receiver(&SOURCE_CHAIN, &sender, &receiver.address);
+
+        println!("router: {:?}", router.address);
+        println!("receiver: {:?}", receiver.address);
+
+        Self {
+            env,
+            owner,
+            router,
+            receiver,
+            oracles,
+            sender,
+        }
+    }
+
+    fn sign(&self, nonce: u64, payload: &Bytes) -> Vec<(BytesN<64>, u32)> {
+        self.sign_from(&self.sender, nonce, payload)
+    }
+
+    fn sign_from(
+        &self,
+        sender: &EthAddress,
+        nonce: u64,
+        payload: &Bytes,
+    ) -> Vec<(BytesN<64>, u32)> {
+        let env = &self.env;
+        vec![
+            env,
+            self.oracles[0].sign_message(
+                env,
+                &self.router.address,
+                SOURCE_CHAIN,
+                sender,
+                nonce,
+                payload,
+            ),
+            self.oracles[2].sign_message(
+                env,
+                &self.router.address,
+                SOURCE_CHAIN,
+                sender,
+                nonce,
+                payload,
+            ),
+        ]
+    }
+}
+
+#[test]
+fn receive_message() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    let payload = bytes!(env, 0xcafebabe);
+    let signatures = setup.sign(0, &payload);
+    router.receive_message(&SOURCE_CHAIN, &setup.sender, &0, &payload, &signatures);
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == router.address)
+        .last()
+        .unwrap();
+    assert_eq!(
+        vec![env, event],
+        vec![
+            env,
+            (
+                router.address.clone(),
+                (MESSAGE_RECEIVED, SOURCE_CHAIN, setup.sender.clone()).into_val(env),
+                MessageReceivedEvent {
+                    version: EVENT_VERSION,
+                    source_chain: SOURCE_CHAIN,
+                    sender: setup.sender.clone(),
+                    nonce: 0,
+                    receiver: setup.receiver.address.clone(),
+                }
+                .into_val(env),
+            )
+        ]
+    );
+
+    assert_eq!(router.get_nonce(&SOURCE_CHAIN, &setup.sender), 1);
+    assert_eq!(
+        setup.receiver.messages(),
+        vec![env, (SOURCE_CHAIN, setup.sender.clone(), payload)]
+    );
+}
+
+#[test]
+fn replay() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    let payload = bytes!(env, 0xcafebabe);
+    let signatures = setup.sign(0, &payload);
+    router.receive_message(&SOURCE_CHAIN, &setup.sender, &0, &payload, &signatures);
+    // the same message can not be delivered twice
+    let result =
+        router.try_receive_message(&SOURCE_CHAIN, &setup.sender, &0, &payload, &signatures);
+    assert_eq!(result, Err(Ok(Error::InvalidNonce)));
+}
+
+#[test]
+fn out_of_order() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    // skipping a nonce is not allowed
+    let payload = bytes!(env, 0xcafebabe);
+    let signatures = setup.sign(1, &payload);
+    let result =
+        router.try_receive_message(&SOURCE_CHAIN, &setup.sender, &1, &payload, &signatures);
+    assert_eq!(result, Err(Ok(Error::InvalidNonce)));
+
+    // nonces are tracked per source chain
+    let other_chain = SOURCE_CHAIN + 1;
+    assert_eq!(router.get_nonce(&other_chain, &setup.sender), 0);
+}
+
+#[test]
+fn unknown_receiver() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    router.remove_receiver(&SOURCE_CHAIN, &setup.sender);
+    let payload = bytes!(env, 0xcafebabe);
+    let signatures = setup.sign(0, &payload);
+    let result =
+        router.try_receive_message(&SOURCE_CHAIN, &setup.sender, &0, &payload, &signatures);
+    assert_eq!(result, Err(Ok(Error::ReceiverUnknown)));
+}
+
+#[test]
+fn tampered_payload() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    let signatures = setup.sign(0, &bytes!(env, 0xcafebabe));
+    let result = router.try_receive_message(
+        &SOURCE_CHAIN,
+        &setup.sender,
+        &0,
+        &bytes!(env, 0xdeadbeef),
+        &signatures,
+    );
+    assert_eq!(result, Err(Ok(Error::UnauthorizedOracleSignatures)));
+}
+
+#[test]
+fn nonce_per_sender() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    // a message from a sender without receiver fails
+    let unknown = EthAddress::from_bytes(BytesN::from_array(env, &[0xcd; 20]));
+    let payload = bytes!(env, 0xcafebabe);
+    let result = router.try_receive_message(
+        &SOURCE_CHAIN,
+        &unknown,
+        &0,
+        &payload,
+        &setup.sign_from(&unknown, 0, &payload),
+    );
+    assert_eq!(result, Err(Ok(Error::ReceiverUnknown)));
+    assert_eq!(router.get_nonce(&SOURCE_CHAIN, &unknown), 0);
+
+    // without blocking the other senders of the source chain
+    router.receive_message(
+        &SOURCE_CHAIN,
+        &setup.sender,
+        &0,
+        &payload,
+        &setup.sign(0, &payload),
+    );
+    assert_eq!(router.get_nonce(&SOURCE_CHAIN, &setup.sender), 1);
+    assert_eq!(setup.receiver.messages().len(), 1);
+}
+
+#[test]
+fn skip_message() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    // the receiver rejects the first message, which blocks the sender
+    let rejected = Bytes::new(env);
+    assert!(router
+        .try_receive_message(
+            &SOURCE_CHAIN,
+            &setup.sender,
+            &0,
+            &rejected,
+            &setup.sign(0, &rejected),
+        )
+        .is_err());
+    let payload = bytes!(env, 0xcafebabe);
+    assert_eq!(
+        router.try_receive_message(
+            &SOURCE_CHAIN,
+            &setup.sender,
+            &1,
+            &payload,
+            &setup.sign(1, &payload),
+        ),
+        Err(Ok(Error::InvalidNonce))
+    );
+
+    // only the expected nonce can be skipped
+    assert_eq!(
+        router.try_skip_message(&SOURCE_CHAIN, &setup.sender, &1),
+        Err(Ok(Error::InvalidNonce))
+    );
+    router.skip_message(&SOURCE_CHAIN, &setup.sender, &0);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            setup.owner.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    router.address.clone(),
+                    Symbol::new(env, "skip_message"),
+                    (SOURCE_CHAIN, setup.sender.clone(), 0_u64).into_val(env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        vec![env, event],
+        vec![
+            env,
+            (
+                router.address.clone(),
+                (MESSAGE_SKIPPED, SOURCE_CHAIN, setup.sender.clone()).into_val(env),
+                MessageSkippedEvent {
+                    version: EVENT_VERSION,
+                    source_chain: SOURCE_CHAIN,
+                    sender: setup.sender.clone(),
+                    nonce: 0,
+                }
+                .into_val(env),
+            )
+        ]
+    );
+
+    // the following messages are delivered again
+    router.receive_message(
+        &SOURCE_CHAIN,
+        &setup.sender,
+        &1,
+        &payload,
+        &setup.sign(1, &payload),
+    );
+    assert_eq!(
+        setup.receiver.messages(),
+        vec![env, (SOURCE_CHAIN, setup.sender.clone(), payload)]
+    );
+}
+
+#[test]
+fn rotate_oracles() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let router = &setup.router;
+
+    // the owner replaces the oracles
+    let oracle = TestOracleGenerator::new()
+        .generate_sorted(env, 4)
+        .into_iter()
+        .find(|oracle| {
+            setup
+                .oracles
+                .iter()
+                .all(|previous| previous.address(env) != oracle.address(env))
+        })
+        .unwrap();
+    router.set_oracle_config(
+        &vec![env, oracle.address(env)],
+        &1,
+        &env.ledger().sequence(),
+    );
+
+    // messages signed by the previous oracles are rejected
+    let payload = bytes!(env, 0xcafebabe);
+    assert_eq!(
+        router.try_receive_message(
+            &SOURCE_CHAIN,
+            &setup.sender,
+            &0,
+            &payload,
+            &setup.sign(0, &payload),
+        ),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    let signatures = vec![
+        env,
+        oracle.sign_message(
+            env,
+            &router.address,
+            SOURCE_CHAIN,
+            &setup.sender,
+            0,
+            &payload,
+        ),
+    ];
+    router.receive_message(&SOURCE_CHAIN, &setup.sender, &0, &payload, &signatures);
+    assert_eq!(setup.receiver.messages().len(), 1);
+}
@@ -0,0 +1,45 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, Env, Vec};
+
+use relink::{EthAddress, MessageReceiver};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Router,
+    Messages,
+}
+
+#[contract]
+pub struct TestReceiver;
+
+#[contractimpl]
+impl TestReceiver {
+    /// Initialize contract by setting the message receiver contract allowed to deliver messages.
+    pub fn initialize(env: Env, router: Address) {
+        env.storage().instance().set(&DataKey::Router, &router);
+    }
+
+    /// Return all messages handled so far.
+    pub fn messages(env: Env) -> Vec<(u64, EthAddress, Bytes)> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Messages)
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+#[contractimpl]
+impl MessageReceiver for TestReceiver {
+    /// Record the message, empty payloads are rejected.
+    fn handle_message(env: Env, source_chain: u64, sender: EthAddress, payload: Bytes) {
+        if payload.is_empty() {
+            panic!("empty payload");
+        }
+        // only messages delivered by the message receiver contract are authentic
+        let router: Address = env.storage().instance().get(&DataKey::Router).unwrap();
+        router.require_auth();
+        let mut messages = Self::messages(env.clone());
+        messages.push_back((source_chain, sender, payload));
+        env.storage().instance().set(&DataKey::Messages, &messages);
+    }
+}
@@ -0,0 +1,94 @@
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use relink::events::{
+    PoolConfigSetEvent, PoolRefilledEvent, RandomnessDrawnEvent, WhitelistAddedEvent,
+    WhitelistRemovedEvent, WithdrawnEvent, EVENT_VERSION, POOL_CONFIG_SET, POOL_REFILLED,
+    RANDOMNESS_DRAWN, WHITELIST_ADDED, WHITELIST_REMOVED, WITHDRAWN,
+};
+use relink::RequestId;
+
+use crate::storage::PoolConfig;
+
+pub(crate) fn pool_config_set(env: &Env, config: PoolConfig) {
+    let topics = (POOL_CONFIG_SET,);
+    env.events().publish(
+        topics,
+        PoolConfigSetEvent {
+            version: EVENT_VERSION,
+            token: config.token,
+            draw_fee: config.draw_fee,
+            low_water_mark: config.low_water_mark,
+            refill_words: config.refill_words,
+            refill_fee: config.refill_fee,
+        },
+    );
+}
+
+pub(crate) fn dapp_added(env: &Env, dapp: Address) {
+    let topics = (WHITELIST_ADDED, dapp.clone());
+    env.events().publish(
+        topics,
+        WhitelistAddedEvent {
+            version: EVENT_VERSION,
+            address: dapp,
+        },
+    );
+}
+
+pub(crate) fn dapp_removed(env: &Env, dapp: Address) {
+    let topics = (WHITELIST_REMOVED, dapp.clone());
+    env.events().publish(
+        topics,
+        WhitelistRemovedEvent {
+            version: EVENT_VERSION,
+            address: dapp,
+        },
+    );
+}
+
+pub(crate) fn pool_refilled(env: &Env, dapp: Address, id: RequestId, count: u32, available: u32) {
+    let topics = (POOL_REFILLED, dapp.clone(), id.clone());
+    env.events().publish(
+        topics,
+        PoolRefilledEvent {
+            version: EVENT_VERSION,
+            dapp,
+            id,
+            count,
+            available,
+        },
+    );
+}
+
+pub(crate) fn randomness_drawn(
+    env: &Env,
+    dapp: Address,
+    first_index: u64,
+    random_words: Vec<BytesN<32>>,
+    fee: i128,
+) {
+    let topics = (RANDOMNESS_DRAWN, dapp.clone());
+    env.events().publish(
+        topics,
+        RandomnessDrawnEvent {
+            version: EVENT_VERSION,
+            dapp,
+            first_index,
+            random_words,
+            fee,
+        },
+    );
+}
+
+pub(crate) fn withdrawn(env: &Env, token: Address, to: Address, amount: i128) {
+    let topics = (WITHDRAWN, token.clone());
+    env.events().publish(
+        topics,
+        WithdrawnEvent {
+            version: EVENT_VERSION,
+            token,
+            to,
+            amount,
+        },
+    );
+}
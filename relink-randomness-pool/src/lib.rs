@@ -0,0 +1,201 @@
+//! Pool of verified random words which dapps can draw from immediately, without waiting for the
+//! round-trip of a randomness request. The pool is a VRF consumer which requests more words from
+//! the proxy, paid from its own balance, whenever fewer than the low-water mark are left.
+//!
+//! Only dapps registered by the owner can draw, and every dapp has its own buffer, so no other
+//! account can change which word a dapp gets next. The buffered words are still part of the
+//! public ledger state before they are drawn: anyone can read the next words of a dapp. Dapps
+//! have to make sure nobody can profit from knowing them, for example by not letting the account
+//! which triggers a draw decide whether or when to draw.
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, Vec};
+
+use relink::{
+    confirmed_owner, consumer, consumer_admin, impl_confirmed_owner, impl_vrf_consumer_admin,
+    pending_requests, vrf_consumer, ConfirmedOwner, Error, EthAddress, FulfillRandomness,
+    RequestId, VrfConsumerAdmin, VrfDirectFundingConsumer,
+};
+
+pub use storage::PoolConfig;
+
+pub mod events;
+mod storage;
+mod test;
+
+#[contract]
+pub struct RelinkRandomnessPool;
+
+impl_confirmed_owner!(RelinkRandomnessPool);
+impl_vrf_consumer_admin!(RelinkRandomnessPool);
+
+#[contractimpl]
+impl RelinkRandomnessPool {
+    /// Initialize contract by setting the owner, the proxy address, trusted oracles and the
+    /// pool configuration.
+    pub fn initialize(
+        env: Env,
+        owner: Address,
+        proxy: Address,
+        threshold: u32,
+        oracles: Vec<EthAddress>,
+        config: PoolConfig,
+    ) -> Result<(), Error> {
+        config.validate()?;
+        vrf_consumer::initialize(&env, &owner, &proxy, threshold, oracles, None)?;
+        storage::set_config(&env, &config);
+        events::pool_config_set(&env, config);
+        Ok(())
+    }
+
+    /// Update the fees and refill parameters. Must be authorized by the owner.
+    pub fn set_config(env: Env, config: PoolConfig) -> Result<(), Error> {
+        confirmed_owner::require_owner(&env);
+        config.validate()?;
+        storage::set_config(&env, &config);
+        events::pool_config_set(&env, config);
+        Ok(())
+    }
+
+    pub fn get_config(env: Env) -> PoolConfig {
+        storage::get_config(&env)
+    }
+
+    /// Allow a dapp to draw words, it gets its own buffer. Must be authorized by the owner.
+    pub fn add_dapp(env: Env, dapp: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::add_dapp(&env, dapp.clone());
+        events::dapp_added(&env, dapp);
+    }
+
+    /// Stop a dapp from drawing, its buffered words are kept. Must be authorized by the owner.
+    pub fn remove_dapp(env: Env, dapp: Address) {
+        confirmed_owner::require_owner(&env);
+        storage::remove_dapp(&env, dapp.clone());
+        events::dapp_removed(&env, dapp);
+    }
+
+    /// Check whether the dapp is allowed to draw words.
+    pub fn is_dapp(env: Env, dapp: Address) -> bool {
+        storage::is_dapp(&env, dapp)
+    }
+
+    /// Return the number of words the dapp can draw.
+    pub fn available(env: Env, dapp: Address) -> u32 {
+        storage::get_words(&env, dapp).len()
+    }
+
+    /// Return the number of words the dapp drew.
+    pub fn get_drawn_count(env: Env, dapp: Address) -> u64 {
+        storage::get_drawn_count(&env, dapp)
+    }
+
+    /// Return the id of the refill request the buffer of the dapp is waiting for.
+    pub fn get_pending_refill(env: Env, dapp: Address) -> Option<RequestId> {
+        storage::get_pending_refill(&env, dapp)
+    }
+
+    /// Take `count` words out of the buffer of the dapp, every word is served only once. The dapp
+    /// has to be registered and pays the draw fee per word.
+    pub fn draw(env: Env, dapp: Address, count: u32) -> Result<Vec<BytesN<32>>, Error> {
+        dapp.require_auth();
+        storage::require_dapp(&env, dapp.clone())?;
+        if count == 0 {
+            return Err(Error::InvalidDrawCount);
+        }
+        let words = storage::get_words(&env, dapp.clone());
+        if count > words.len() {
+            return Err(Error::PoolExhausted);
+        }
+        let config = storage::get_config(&env);
+        let fee = config.draw_fee * count as i128;
+        if fee > 0 {
+            let client = token::Client::new(&env, &config.token);
+            client.transfer(&dapp, &env.current_contract_address(), &fee);
+        }
+        // remove the drawn words before anything else happens
+        let drawn = words.slice(0..count);
+        storage::set_words(&env, dapp.clone(), &words.slice(count..));
+        let first_index = storage::get_drawn_count(&env, dapp.clone());
+        storage::add_drawn_count(&env, dapp.clone(), count);
+        events::randomness_drawn(&env, dapp.clone(), first_index, drawn.clone(), fee);
+        refill_if_low(&env, &dapp);
+        Ok(drawn)
+    }
+
+    /// Request more words for the dapp if its buffer is below the low-water mark and no refill
+    /// is pending. Anyone may call this, the request is paid from the pool's balance.
+    pub fn refill(env: Env, dapp: Address) -> Result<Option<RequestId>, Error> {
+        storage::require_dapp(&env, dapp.clone())?;
+        Ok(refill_if_low(&env, &dapp))
+    }
+
+    /// Withdraw tokens from the pool's balance to the owner. Must be authorized by the owner.
+    pub fn withdraw(env: Env, amount: i128) {
+        confirmed_owner::require_owner(&env);
+        let token = storage::get_config(&env).token;
+        let owner = confirmed_owner::owner(&env);
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &owner, &amount);
+        events::withdrawn(&env, token, owner, amount);
+    }
+}
+
+impl FulfillRandomness for RelinkRandomnessPool {
+    /// Add the words of a verified refill to the buffer of the dapp it was requested for.
+    fn fulfill_randomness(
+        env: &Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        let dapp = storage::get_refill_dapp(env, id.clone()).ok_or(Error::RequestUnknown)?;
+        let mut words = storage::get_words(env, dapp.clone());
+        words.append(&random_words);
+        storage::set_words(env, dapp.clone(), &words);
+        storage::remove_refill(env, dapp.clone(), id.clone());
+        events::pool_refilled(env, dapp, id, random_words.len(), words.len());
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl VrfDirectFundingConsumer for RelinkRandomnessPool {
+    /// Process the response to a refill request.
+    fn verify_and_fulfill_randomness(
+        env: Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        vrf_consumer::verify_and_fulfill_randomness::<Self>(&env, id, random_words, signatures)
+    }
+}
+
+fn refill_if_low(env: &Env, dapp: &Address) -> Option<RequestId> {
+    let config = storage::get_config(env);
+    if storage::get_words(env, dapp.clone()).len() >= config.low_water_mark {
+        return None;
+    }
+    // a refill whose pending request expired is not waited for anymore
+    if let Some(id) = storage::get_pending_refill(env, dapp.clone()) {
+        if pending_requests::has(env, id) {
+            return None;
+        }
+    }
+    // skip the refill rather than failing the draw if the pool cannot pay for it or the proxy
+    // rejects the request
+    let balance = token::Client::new(env, &config.token).balance(&env.current_contract_address());
+    if balance < config.refill_fee {
+        return None;
+    }
+    let id = consumer::request_randomness_from_contract(
+        env,
+        &config.token,
+        config.refill_fee,
+        None,
+        Some(config.refill_words),
+    )
+    .ok()?;
+    pending_requests::add(env, id.clone());
+    storage::set_pending_refill(env, dapp.clone(), &id);
+    Some(id)
+}
@@ -0,0 +1,158 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Val, Vec};
+
+use relink::pending_requests::REQUEST_TTL;
+use relink::{Error, RequestId};
+
+const DAPP_BUMP_AMOUNT: u32 = 518400; // 30 days
+/// Maximum number of random words per request to the proxy.
+const MAX_REFILL_WORDS: u32 = 10;
+/// Upper bound of the low-water mark, keeps the word buffer of a dapp small.
+const MAX_LOW_WATER_MARK: u32 = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PoolConfig {
+    /// Token the draw fees are paid in, has to be the fee token of the proxy.
+    pub token: Address,
+    /// Fee per drawn word.
+    pub draw_fee: i128,
+    /// The pool requests more words for a dapp when fewer than this are available to it.
+    pub low_water_mark: u32,
+    /// Number of words requested per refill, at most 10.
+    pub refill_words: u32,
+    /// Fee paid to the proxy per refill.
+    pub refill_fee: i128,
+}
+
+impl PoolConfig {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.draw_fee < 0
+            || self.refill_fee < 0
+            || self.refill_words == 0
+            || self.refill_words > MAX_REFILL_WORDS
+            || self.low_water_mark > MAX_LOW_WATER_MARK
+        {
+            return Err(Error::InvalidPoolConfig);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKeyRandomnessPool {
+    Config,
+    Dapp(Address),
+    Words(Address),
+    DrawnCount(Address),
+    PendingRefill(Address),
+    Refill(RequestId),
+}
+
+pub fn set_config(env: &Env, config: &PoolConfig) {
+    env.storage()
+        .instance()
+        .set(&DataKeyRandomnessPool::Config, config);
+}
+
+pub fn get_config(env: &Env) -> PoolConfig {
+    env.storage()
+        .instance()
+        .get(&DataKeyRandomnessPool::Config)
+        .unwrap()
+}
+
+/// Write a persistent entry of a dapp and extend its TTL.
+fn set_dapp_entry<V: IntoVal<Env, Val>>(env: &Env, key: &DataKeyRandomnessPool, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, DAPP_BUMP_AMOUNT, DAPP_BUMP_AMOUNT);
+}
+
+pub fn add_dapp(env: &Env, dapp: Address) {
+    set_dapp_entry(env, &DataKeyRandomnessPool::Dapp(dapp), &());
+}
+
+pub fn remove_dapp(env: &Env, dapp: Address) {
+    let key = DataKeyRandomnessPool::Dapp(dapp);
+    env.storage().persistent().remove(&key);
+}
+
+pub fn is_dapp(env: &Env, dapp: Address) -> bool {
+    let key = DataKeyRandomnessPool::Dapp(dapp);
+    env.storage().persistent().has(&key)
+}
+
+/// Return an error if the owner did not register the dapp, otherwise extend the TTL of the
+/// registration so that it does not expire while the dapp is in use.
+pub fn require_dapp(env: &Env, dapp: Address) -> Result<(), Error> {
+    let key = DataKeyRandomnessPool::Dapp(dapp);
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::DappUnknown);
+    }
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, DAPP_BUMP_AMOUNT, DAPP_BUMP_AMOUNT);
+    Ok(())
+}
+
+/// Return the verified words of the dapp which were not drawn yet, oldest first.
+pub fn get_words(env: &Env, dapp: Address) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKeyRandomnessPool::Words(dapp))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_words(env: &Env, dapp: Address, words: &Vec<BytesN<32>>) {
+    set_dapp_entry(env, &DataKeyRandomnessPool::Words(dapp), words);
+}
+
+/// Return the number of words the dapp drew.
+pub fn get_drawn_count(env: &Env, dapp: Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKeyRandomnessPool::DrawnCount(dapp))
+        .unwrap_or(0)
+}
+
+pub fn add_drawn_count(env: &Env, dapp: Address, count: u32) {
+    set_dapp_entry(
+        env,
+        &DataKeyRandomnessPool::DrawnCount(dapp.clone()),
+        &(get_drawn_count(env, dapp) + count as u64),
+    );
+}
+
+pub fn get_pending_refill(env: &Env, dapp: Address) -> Option<RequestId> {
+    env.storage()
+        .persistent()
+        .get(&DataKeyRandomnessPool::PendingRefill(dapp))
+}
+
+/// Remember the refill request of the dapp, and the dapp the words of the request are for.
+pub fn set_pending_refill(env: &Env, dapp: Address, id: &RequestId) {
+    set_dapp_entry(env, &DataKeyRandomnessPool::PendingRefill(dapp.clone()), id);
+    let key = DataKeyRandomnessPool::Refill(id.clone());
+    env.storage().temporary().set(&key, &dapp);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, REQUEST_TTL, REQUEST_TTL);
+}
+
+/// Return the dapp a refill request was made for.
+pub fn get_refill_dapp(env: &Env, id: RequestId) -> Option<Address> {
+    let key = DataKeyRandomnessPool::Refill(id);
+    env.storage().temporary().get(&key)
+}
+
+pub fn remove_refill(env: &Env, dapp: Address, id: RequestId) {
+    if get_pending_refill(env, dapp.clone()) == Some(id.clone()) {
+        env.storage()
+            .persistent()
+            .remove(&DataKeyRandomnessPool::PendingRefill(dapp));
+    }
+    let key = DataKeyRandomnessPool::Refill(id);
+    env.storage().temporary().remove(&key);
+}
@@ -0,0 +1,255 @@
+extern crate std;
+
+use std::println;
+
+use relink::event_decoder::{decode, RelinkEvent};
+use relink::events::{RandomnessDrawnEvent, EVENT_VERSION};
+use relink::testutils::{TestOracle, TestOracleGenerator};
+use relink::{Error, RequestId};
+use relink_vrf_direct_funding_proxy::{
+    RelinkVrfDirectFundingProxy, RelinkVrfDirectFundingProxyClient,
+};
+use soroban_sdk::testutils::{Address as AddressTestTrait, Events};
+use soroban_sdk::{token, vec, Address, BytesN, Env, Vec};
+
+use crate::{PoolConfig, RelinkRandomnessPool, RelinkRandomnessPoolClient};
+
+struct Setup<'a> {
+    env: Env,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    proxy: RelinkVrfDirectFundingProxyClient<'a>,
+    pool: RelinkRandomnessPoolClient<'a>,
+    owner: Address,
+    backend: Address,
+    oracle: TestOracle,
+}
+
+impl Setup<'_> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        // Create the token contract
+        let token_owner = Address::generate(&env);
+        let (token, token_admin) = relink::testutils::create_token_contract(&env, &token_owner);
+
+        // Create and initialize the proxy contract
+        let proxy_id = env.register_contract(None, RelinkVrfDirectFundingProxy);
+        let proxy = RelinkVrfDirectFundingProxyClient::new(&env, &proxy_id);
+        proxy.initialize(&Address::generate(&env), &token.address, &10);
+        let backend = Address::generate(&env);
+        proxy.add_backend_whitelist(&backend);
+
+        // Create and initialize the pool contract
+        let oracle = TestOracleGenerator::new()
+            .generate_sorted(&env, 1)
+            .pop()
+            .unwrap();
+        let pool_id = env.register_contract(None, RelinkRandomnessPool);
+        let pool = RelinkRandomnessPoolClient::new(&env, &pool_id);
+        let owner = Address::generate(&env);
+        let config = PoolConfig {
+            token: token.address.clone(),
+            draw_fee: 2,
+            low_water_mark: 3,
+            refill_words: 4,
+            refill_fee: 10,
+        };
+        pool.initialize(
+            &owner,
+            &proxy.address,
+            &1,
+            &vec![&env, oracle.address(&env)],
+            &config,
+        );
+
+        println!("proxy: {:?}", proxy.address);
+        println!("pool: {:?}", pool.address);
+
+        Self {
+            env,
+            token,
+            token_admin,
+            proxy,
+            pool,
+            owner,
+            backend,
+            oracle,
+        }
+    }
+
+    fn random_account(&self, amount: &i128) -> Address {
+        let account = Address::generate(&self.env);
+        self.token_admin.mint(&account, amount);
+        account
+    }
+
+    /// Deliver `count` random words for the refill request, starting at `seed`.
+    fn fulfill(&self, id: &RequestId, seed: u8, count: u8) -> Vec<BytesN<32>> {
+        let env = &self.env;
+        let mut random_words = Vec::new(env);
+        for i in 0..count {
+            random_words.push_back(BytesN::from_array(env, &[seed + i; 32]));
+        }
+        let signatures = vec![
+            env,
+            self.oracle.sign(env, &self.pool.address, id, &random_words),
+        ];
+        self.proxy
+            .callback_with_randomness(&self.backend, id, &random_words, &signatures);
+        random_words
+    }
+}
+
+#[test]
+fn draw_and_refill() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let pool = &setup.pool;
+    setup.token_admin.mint(&pool.address, &100);
+    let dapp = setup.random_account(&100);
+    pool.add_dapp(&dapp);
+
+    // the empty buffer requests words, but only once at a time
+    let id = pool.refill(&dapp).unwrap();
+    assert_eq!(pool.get_pending_refill(&dapp), Some(id.clone()));
+    assert_eq!(pool.refill(&dapp), None);
+    assert_eq!(setup.token.balance(&setup.proxy.address), 10);
+    let words = setup.fulfill(&id, 1, 4);
+    assert_eq!(pool.available(&dapp), 4);
+    assert_eq!(pool.get_pending_refill(&dapp), None);
+
+    let drawn = pool.draw(&dapp, &2);
+    assert_eq!(drawn, words.slice(0..2));
+    assert_eq!(setup.token.balance(&dapp), 96);
+    // the draw is recorded with its position among all drawn words
+    let drawn_events: std::vec::Vec<RelinkEvent> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| *contract == pool.address)
+        .filter_map(|(_, topics, data)| match decode(env, &topics, &data) {
+            Ok(event @ RelinkEvent::RandomnessDrawn(_)) => Some(event),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        drawn_events,
+        std::vec![RelinkEvent::RandomnessDrawn(RandomnessDrawnEvent {
+            version: EVENT_VERSION,
+            dapp: dapp.clone(),
+            first_index: 0,
+            random_words: drawn,
+            fee: 4,
+        })]
+    );
+
+    // falling below the low-water mark requested a refill
+    let id = pool.get_pending_refill(&dapp).unwrap();
+    assert_eq!(pool.available(&dapp), 2);
+    assert_eq!(pool.try_draw(&dapp, &3), Err(Ok(Error::PoolExhausted)));
+    // words are served in order and only once
+    assert_eq!(pool.draw(&dapp, &1), words.slice(2..3));
+    let more = setup.fulfill(&id, 9, 4);
+    let mut expected = words.slice(3..4);
+    expected.append(&more.slice(0..2));
+    assert_eq!(pool.draw(&dapp, &3), expected);
+    assert_eq!(pool.get_drawn_count(&dapp), 6);
+    assert_eq!(pool.available(&dapp), 2);
+}
+
+#[test]
+fn dapps() {
+    let setup = Setup::new();
+    let pool = &setup.pool;
+    setup.token_admin.mint(&pool.address, &100);
+    let dapp = setup.random_account(&100);
+    let other = setup.random_account(&100);
+
+    // only registered dapps can draw or refill
+    assert_eq!(pool.try_draw(&dapp, &1), Err(Ok(Error::DappUnknown)));
+    assert_eq!(pool.try_refill(&dapp), Err(Ok(Error::DappUnknown)));
+    pool.add_dapp(&dapp);
+    pool.add_dapp(&other);
+    assert!(pool.is_dapp(&dapp));
+    assert_eq!(pool.try_draw(&dapp, &0), Err(Ok(Error::InvalidDrawCount)));
+
+    // every dapp draws from its own buffer
+    let id = pool.refill(&dapp).unwrap();
+    let words = setup.fulfill(&id, 1, 4);
+    let other_id = pool.refill(&other).unwrap();
+    let other_words = setup.fulfill(&other_id, 20, 4);
+    assert_eq!(pool.draw(&other, &3), other_words.slice(0..3));
+    assert_eq!(pool.draw(&dapp, &1), words.slice(0..1));
+    assert_eq!(pool.available(&dapp), 3);
+    assert_eq!(pool.available(&other), 1);
+
+    pool.remove_dapp(&other);
+    assert!(!pool.is_dapp(&other));
+    assert_eq!(pool.try_draw(&other, &1), Err(Ok(Error::DappUnknown)));
+}
+
+#[test]
+fn mismatched_token() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let pool = &setup.pool;
+    setup.token_admin.mint(&pool.address, &100);
+    let dapp = setup.random_account(&100);
+    pool.add_dapp(&dapp);
+    let id = pool.refill(&dapp).unwrap();
+    setup.fulfill(&id, 1, 4);
+
+    // a token the proxy doesn't charge in fails the refill, but not the draw
+    let (other_token, other_admin) =
+        relink::testutils::create_token_contract(env, &Address::generate(env));
+    other_admin.mint(&pool.address, &100);
+    other_admin.mint(&dapp, &100);
+    let mut config = pool.get_config();
+    config.token = other_token.address.clone();
+    pool.set_config(&config);
+    assert_eq!(pool.draw(&dapp, &2).len(), 2);
+    assert_eq!(pool.get_pending_refill(&dapp), None);
+    assert_eq!(other_token.balance(&dapp), 96);
+    assert_eq!(setup.token.balance(&setup.proxy.address), 10);
+}
+
+#[test]
+fn unfunded_pool() {
+    let setup = Setup::new();
+    let pool = &setup.pool;
+    let dapp = Address::generate(&setup.env);
+    pool.add_dapp(&dapp);
+    // without a balance to pay the proxy fee no refill is requested
+    assert_eq!(pool.refill(&dapp), None);
+    setup.token_admin.mint(&pool.address, &9);
+    assert_eq!(pool.refill(&dapp), None);
+    setup.token_admin.mint(&pool.address, &1);
+    // the pool authorizes the fee transfer itself
+    setup.env.mock_auths(&[]);
+    assert!(pool.refill(&dapp).is_some());
+    assert_eq!(setup.token.balance(&pool.address), 0);
+}
+
+#[test]
+fn config() {
+    let setup = Setup::new();
+    let pool = &setup.pool;
+    let mut config = pool.get_config();
+    config.refill_words = 11;
+    assert_eq!(
+        pool.try_set_config(&config),
+        Err(Ok(Error::InvalidPoolConfig))
+    );
+    config.refill_words = 10;
+    config.draw_fee = 0;
+    pool.set_config(&config);
+    assert_eq!(pool.get_config(), config);
+
+    // draw fees can be withdrawn by the owner
+    setup.token_admin.mint(&pool.address, &25);
+    pool.withdraw(&20);
+    assert_eq!(setup.token.balance(&setup.owner), 20);
+    assert_eq!(setup.token.balance(&pool.address), 5);
+}
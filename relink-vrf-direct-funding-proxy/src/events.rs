@@ -1,15 +1,13 @@
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, Vec};
 
+use relink::events::{
+    CallbackFailedEvent, FeeSetEvent, PausedSetEvent, RandomnessProvidedEvent,
+    RandomnessRequestedEvent, RequestCancelledEvent, WhitelistAddedEvent, WhitelistRemovedEvent,
+    WithdrawnEvent, CALLBACK_FAILED, EVENT_VERSION, FEE_SET, PAUSED_SET, RANDOMNESS_PROVIDED,
+    RANDOMNESS_REQUESTED, REQUEST_CANCELLED, WHITELIST_ADDED, WHITELIST_REMOVED, WITHDRAWN,
+};
 use relink::RequestId;
 
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
-#[contracttype]
-pub struct RandomnessRequestData {
-    pub id: RequestId,
-    pub request_confirmations: u32,
-    pub num_words: u32,
-}
-
 /// Equivalent event to the one in Solidity:
 /// event RandomnessRequest(
 ///     address indexed user,
@@ -28,10 +26,14 @@ pub(crate) fn randomness_requested(
     request_confirmations: u32,
     num_words: u32,
 ) {
-    let topics = (symbol_short!("request"), origin, dapp, nonce);
+    let topics = (RANDOMNESS_REQUESTED, origin.clone(), dapp.clone(), nonce);
     env.events().publish(
         topics,
-        RandomnessRequestData {
+        RandomnessRequestedEvent {
+            version: EVENT_VERSION,
+            origin,
+            dapp,
+            nonce,
             id,
             request_confirmations,
             num_words,
@@ -39,22 +41,100 @@ pub(crate) fn randomness_requested(
     );
 }
 
-pub(crate) fn randomness_provided(env: &Env, id: RequestId, random_words: Vec<BytesN<32>>) {
-    let topics = (symbol_short!("response"), id);
-    env.events().publish(topics, random_words);
+pub(crate) fn randomness_provided(
+    env: &Env,
+    id: RequestId,
+    backend: Address,
+    random_words: Vec<BytesN<32>>,
+) {
+    let topics = (RANDOMNESS_PROVIDED, id.clone());
+    env.events().publish(
+        topics,
+        RandomnessProvidedEvent {
+            version: EVENT_VERSION,
+            id,
+            backend,
+            random_words,
+        },
+    );
+}
+
+pub(crate) fn request_cancelled(env: &Env, id: RequestId) {
+    let topics = (REQUEST_CANCELLED, id.clone());
+    env.events().publish(
+        topics,
+        RequestCancelledEvent {
+            version: EVENT_VERSION,
+            id,
+        },
+    );
+}
+
+pub(crate) fn callback_failed(env: &Env, id: RequestId, backend: Address) {
+    let topics = (CALLBACK_FAILED, id.clone());
+    env.events().publish(
+        topics,
+        CallbackFailedEvent {
+            version: EVENT_VERSION,
+            id,
+            backend,
+        },
+    );
 }
 
 pub(crate) fn fee_set(env: &Env, fee: i128) {
-    let topics = (symbol_short!("fee_set"), fee);
-    env.events().publish(topics, ());
+    let topics = (FEE_SET,);
+    env.events().publish(
+        topics,
+        FeeSetEvent {
+            version: EVENT_VERSION,
+            fee,
+        },
+    );
 }
 
 pub(crate) fn whitelist_address_added(env: &Env, address: Address) {
-    let topics = (symbol_short!("wl_add"), address);
-    env.events().publish(topics, ());
+    let topics = (WHITELIST_ADDED, address.clone());
+    env.events().publish(
+        topics,
+        WhitelistAddedEvent {
+            version: EVENT_VERSION,
+            address,
+        },
+    );
 }
 
 pub(crate) fn whitelist_address_removed(env: &Env, address: Address) {
-    let topics = (symbol_short!("wl_rm"), address);
-    env.events().publish(topics, ());
+    let topics = (WHITELIST_REMOVED, address.clone());
+    env.events().publish(
+        topics,
+        WhitelistRemovedEvent {
+            version: EVENT_VERSION,
+            address,
+        },
+    );
+}
+
+pub(crate) fn withdrawn(env: &Env, token: Address, to: Address, amount: i128) {
+    let topics = (WITHDRAWN, token.clone());
+    env.events().publish(
+        topics,
+        WithdrawnEvent {
+            version: EVENT_VERSION,
+            token,
+            to,
+            amount,
+        },
+    );
+}
+
+pub(crate) fn paused_set(env: &Env, paused: bool) {
+    let topics = (PAUSED_SET,);
+    env.events().publish(
+        topics,
+        PausedSetEvent {
+            version: EVENT_VERSION,
+            paused,
+        },
+    );
 }
@@ -1,12 +1,15 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, Vec};
+use soroban_sdk::{contract, contractimpl, token, Address, Bytes, BytesN, Env, Vec};
 
 use relink::{
-    confirmed_owner, impl_confirmed_owner, ConfirmedOwner, Error, RequestId,
-    VrfDirectFundingConsumerClient, VrfDirectFundingProxy,
+    confirmed_owner, eip712, impl_confirmed_owner, ConfirmedOwner, Error, RequestId,
+    VrfDirectFundingConsumerClient, VrfDirectFundingConsumerV2Client,
+    VrfDirectFundingIndexedConsumerClient, VrfDirectFundingProxy,
 };
 
+pub use storage::{BackendStats, DappStats, ProxyStats};
+
 pub mod events;
 mod storage;
 mod test;
@@ -60,6 +63,78 @@ impl RelinkVrfDirectFundingProxy {
         let balance = client.balance(&this_contract);
         let owner = confirmed_owner::owner(&env);
         client.transfer(&this_contract, &owner, &balance);
+        events::withdrawn(&env, token, owner, balance);
+    }
+
+    /// Stop accepting new requests. Pending requests can still be fulfilled.
+    pub fn pause(env: Env) {
+        confirmed_owner::require_owner(&env);
+        storage::set_paused(&env, true);
+        events::paused_set(&env, true);
+    }
+
+    /// Resume accepting new requests.
+    pub fn unpause(env: Env) {
+        confirmed_owner::require_owner(&env);
+        storage::set_paused(&env, false);
+        events::paused_set(&env, false);
+    }
+
+    /// Check whether new requests are currently rejected.
+    pub fn is_paused(env: Env) -> bool {
+        storage::is_paused(&env)
+    }
+
+    /// Cancel a pending request. Must be authorized by the origin that paid for the request.
+    /// The fee is not refunded, it stays with the proxy like the fees of fulfilled requests.
+    pub fn cancel_request(env: Env, id: RequestId) -> Result<(), Error> {
+        let request = storage::get_request(&env, id.clone())?;
+        request.origin.require_auth();
+        storage::remove_request(&env, id.clone());
+        storage::update_stats(&env, |stats| stats.cancelled += 1);
+        storage::update_dapp_stats(&env, request.dapp, |stats| stats.cancelled += 1);
+        events::request_cancelled(&env, id);
+        Ok(())
+    }
+
+    /// Choose how rejected responses of a backend are handled. By default the callback reverts
+    /// with the consumer's error. When enabled, the callback returns `Ok`, the request stays
+    /// pending and the failure is counted and published as `cb_fail`. Must be authorized by the
+    /// backend, which has to be on the whitelist.
+    pub fn set_record_failed_callbacks(
+        env: Env,
+        backend: Address,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        backend.require_auth();
+        storage::is_whitelisted(&env, backend.clone())?;
+        storage::set_record_failed_callbacks(&env, backend, enabled);
+        Ok(())
+    }
+
+    /// Check whether rejected responses of a backend are recorded instead of reverted.
+    pub fn records_failed_callbacks(env: Env, backend: Address) -> bool {
+        storage::records_failed_callbacks(&env, backend)
+    }
+
+    /// Get the usage counters of the proxy.
+    pub fn stats(env: Env) -> ProxyStats {
+        storage::get_stats(&env)
+    }
+
+    /// Get the total amount of fees collected in the given token.
+    pub fn get_fees_collected(env: Env, token: Address) -> i128 {
+        storage::get_fees_collected(&env, token)
+    }
+
+    /// Get the fulfillment counters of a backend.
+    pub fn get_backend_stats(env: Env, backend: Address) -> BackendStats {
+        storage::get_backend_stats(&env, backend)
+    }
+
+    /// Get the request counters of a dapp.
+    pub fn get_dapp_stats(env: Env, dapp: Address) -> DappStats {
+        storage::get_dapp_stats(&env, dapp)
     }
 }
 
@@ -74,6 +149,9 @@ impl VrfDirectFundingProxy for RelinkVrfDirectFundingProxy {
         request_confirmations: u32,
         num_words: u32,
     ) -> Result<RequestId, Error> {
+        if storage::is_paused(&env) {
+            return Err(Error::Paused);
+        }
         // assert a maxmium of 10 words
         if num_words > 10 {
             panic!("Maximum Random Values: 10");
@@ -89,13 +167,25 @@ impl VrfDirectFundingProxy for RelinkVrfDirectFundingProxy {
         let token_id = storage::get_token(&env);
         let client = token::Client::new(&env, &token_id);
         client.transfer(&origin, &env.current_contract_address(), &value);
+        storage::add_fees_collected(&env, token_id, value);
 
         // generate unique request id
         let nonce = storage::get_and_increment_nonce(&env);
         let id = RequestId::new(&env, &origin, &dapp, &env.current_contract_address(), nonce);
 
         // store a mapping of request-id => dapp address
-        storage::set_request_dapp(&env, id.clone(), &dapp);
+        storage::set_request(
+            &env,
+            id.clone(),
+            &storage::PendingRequest {
+                origin: origin.clone(),
+                dapp: dapp.clone(),
+                nonce,
+                ledger: env.ledger().sequence(),
+            },
+        );
+        storage::update_stats(&env, |stats| stats.total_requests += 1);
+        storage::update_dapp_stats(&env, dapp.clone(), |stats| stats.requests += 1);
 
         // emit event
         events::randomness_requested(
@@ -111,7 +201,8 @@ impl VrfDirectFundingProxy for RelinkVrfDirectFundingProxy {
         Ok(id)
     }
 
-    /// Entry point for randomness data coming from a backend.
+    /// Entry point for randomness data coming from a backend. A rejected response reverts,
+    /// unless the backend enabled `set_record_failed_callbacks`.
     fn callback_with_randomness(
         env: Env,
         backend: Address,
@@ -119,17 +210,122 @@ impl VrfDirectFundingProxy for RelinkVrfDirectFundingProxy {
         random_words: Vec<BytesN<32>>,
         signatures: Vec<(BytesN<64>, u32)>,
     ) -> Result<(), Error> {
-        // only backends on the whitelist are allowed to call this
-        backend.require_auth();
-        storage::is_whitelisted(&env, backend)?;
-        // read and then remove originating dapp address, error if not found
-        let dapp = storage::get_request_dapp(&env, id.clone())?;
-        storage::remove_request_dapp(&env, id.clone());
-        // callback to dapp contract with provided random words
-        let dapp_client = VrfDirectFundingConsumerClient::new(&env, &dapp);
-        dapp_client.verify_and_fulfill_randomness(&id, &random_words, &signatures);
-        // emit event containing the provided random words
-        events::randomness_provided(&env, id, random_words);
-        Ok(())
+        handle_callback(
+            &env,
+            backend,
+            id,
+            random_words,
+            |request, id, random_words| {
+                VrfDirectFundingConsumerClient::new(&env, &request.dapp)
+                    .try_verify_and_fulfill_randomness(id, random_words, &signatures)
+            },
+        )
+    }
+
+    /// Entry point for randomness data with signatures in the 65 byte or compact Ethereum format.
+    fn callback_with_randomness_raw(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<Bytes>,
+    ) -> Result<(), Error> {
+        let signatures = eip712::parse_signatures(&env, &signatures)?;
+        Self::callback_with_randomness(env, backend, id, random_words, signatures)
+    }
+
+    /// Entry point for randomness data, passing the origin and nonce of the request along so
+    /// that the consumer can recompute the request id, and the backend so it can record it.
+    fn callback_with_randomness_v2(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        let deliverer = backend.clone();
+        handle_callback(
+            &env,
+            backend,
+            id,
+            random_words,
+            |request, id, random_words| {
+                VrfDirectFundingConsumerV2Client::new(&env, &request.dapp)
+                    .try_verify_and_fulfill_randomness_v2(
+                        id,
+                        &request.origin,
+                        &request.nonce,
+                        &deliverer,
+                        random_words,
+                        &signatures,
+                    )
+            },
+        )
+    }
+
+    /// Entry point for randomness data in the indexed signature format.
+    fn callback_with_randomness_indexed(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signer_bitmap: u128,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        handle_callback(
+            &env,
+            backend,
+            id,
+            random_words,
+            |request, id, random_words| {
+                VrfDirectFundingIndexedConsumerClient::new(&env, &request.dapp)
+                    .try_verify_and_fulfill_indexed(id, random_words, &signer_bitmap, &signatures)
+            },
+        )
+    }
+}
+
+/// Result of a `try_` call to a consumer callback.
+type CallbackResult<C, I> = Result<Result<(), C>, Result<Error, I>>;
+
+/// Deliver the random words of a pending request through `callback` and record the outcome.
+/// A failed callback reverts with the consumer's error, unless the backend opted into recording
+/// failed callbacks in the statistics and events.
+fn handle_callback<C, I>(
+    env: &Env,
+    backend: Address,
+    id: RequestId,
+    random_words: Vec<BytesN<32>>,
+    callback: impl FnOnce(
+        &storage::PendingRequest,
+        &RequestId,
+        &Vec<BytesN<32>>,
+    ) -> CallbackResult<C, I>,
+) -> Result<(), Error> {
+    // only backends on the whitelist are allowed to call this
+    backend.require_auth();
+    storage::is_whitelisted(env, backend.clone())?;
+    // read originating dapp address, error if not found
+    let request = storage::get_request(env, id.clone())?;
+    // callback to dapp contract with provided random words
+    if let Err(error) = callback(&request, &id, &random_words) {
+        if !storage::records_failed_callbacks(env, backend.clone()) {
+            // contract errors of the consumer are passed on, traps are reported as CallbackFailed
+            return Err(error.unwrap_or(Error::CallbackFailed));
+        }
+        // keep the request pending, the failed callback is recorded instead of reverted
+        storage::update_stats(env, |stats| stats.failed_callbacks += 1);
+        storage::update_dapp_stats(env, request.dapp, |stats| stats.failed_callbacks += 1);
+        events::callback_failed(env, id, backend);
+        return Ok(());
     }
+    // the request should only be handled once
+    storage::remove_request(env, id.clone());
+    storage::update_stats(env, |stats| stats.fulfilled += 1);
+    storage::update_dapp_stats(env, request.dapp, |stats| stats.fulfilled += 1);
+    let latency = env.ledger().sequence() - request.ledger;
+    storage::add_backend_fulfillment(env, backend.clone(), latency);
+    // emit event containing the provided random words
+    events::randomness_provided(env, id, backend, random_words);
+    Ok(())
 }
@@ -3,6 +3,44 @@ use soroban_sdk::{contracttype, Address, Env};
 use relink::{Error, RequestId};
 
 const REQUEST_BUMP_AMOUNT: u32 = 34560; // 2 days
+const STATS_BUMP_AMOUNT: u32 = 518400; // 30 days
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PendingRequest {
+    pub origin: Address,
+    pub dapp: Address,
+    pub nonce: u128,
+    /// Ledger sequence at which the request was made.
+    pub ledger: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[contracttype]
+pub struct ProxyStats {
+    pub total_requests: u64,
+    pub fulfilled: u64,
+    pub cancelled: u64,
+    pub failed_callbacks: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[contracttype]
+pub struct BackendStats {
+    pub fulfilled: u64,
+    /// Sum of the ledgers between request and fulfillment over all fulfilled requests.
+    pub total_latency: u64,
+    pub average_latency: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[contracttype]
+pub struct DappStats {
+    pub requests: u64,
+    pub fulfilled: u64,
+    pub cancelled: u64,
+    pub failed_callbacks: u64,
+}
 
 #[derive(Clone)]
 #[contracttype]
@@ -10,8 +48,14 @@ pub enum DataKeyProxy {
     Token,
     Fee,
     Nonce,
+    Paused,
     Whitelist(Address),
+    RecordFailedCallbacks(Address),
     RequestDapp(RequestId),
+    Stats,
+    FeesCollected(Address),
+    BackendStats(Address),
+    DappStats(Address),
 }
 
 pub fn set_token(env: &Env, token: &Address) {
@@ -36,6 +80,17 @@ pub fn get_fee(env: &Env) -> i128 {
         .unwrap()
 }
 
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKeyProxy::Paused, &paused);
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, bool>(&DataKeyProxy::Paused)
+        .unwrap_or(false)
+}
+
 pub fn get_and_increment_nonce(env: &Env) -> u128 {
     // get value, default to zero
     let value = env
@@ -69,15 +124,29 @@ pub fn is_whitelisted(env: &Env, address: Address) -> Result<(), Error> {
         .ok_or(Error::UnauthorizedBackend)
 }
 
-pub fn set_request_dapp(env: &Env, id: RequestId, dapp: &Address) {
+pub fn set_record_failed_callbacks(env: &Env, backend: Address, enabled: bool) {
+    let key = DataKeyProxy::RecordFailedCallbacks(backend);
+    if enabled {
+        env.storage().instance().set(&key, &());
+    } else {
+        env.storage().instance().remove(&key);
+    }
+}
+
+pub fn records_failed_callbacks(env: &Env, backend: Address) -> bool {
+    let key = DataKeyProxy::RecordFailedCallbacks(backend);
+    env.storage().instance().has(&key)
+}
+
+pub fn set_request(env: &Env, id: RequestId, request: &PendingRequest) {
     let key = DataKeyProxy::RequestDapp(id);
-    env.storage().temporary().set(&key, dapp);
+    env.storage().temporary().set(&key, request);
     env.storage()
         .temporary()
         .extend_ttl(&key, REQUEST_BUMP_AMOUNT, REQUEST_BUMP_AMOUNT);
 }
 
-pub fn get_request_dapp(env: &Env, id: RequestId) -> Result<Address, Error> {
+pub fn get_request(env: &Env, id: RequestId) -> Result<PendingRequest, Error> {
     let key = DataKeyProxy::RequestDapp(id);
     env.storage()
         .temporary()
@@ -85,7 +154,68 @@ pub fn get_request_dapp(env: &Env, id: RequestId) -> Result<Address, Error> {
         .ok_or(Error::RequestUnknown)
 }
 
-pub fn remove_request_dapp(env: &Env, id: RequestId) {
+pub fn remove_request(env: &Env, id: RequestId) {
     let key = DataKeyProxy::RequestDapp(id);
     env.storage().temporary().remove(&key);
 }
+
+pub fn get_stats(env: &Env) -> ProxyStats {
+    env.storage()
+        .instance()
+        .get(&DataKeyProxy::Stats)
+        .unwrap_or_default()
+}
+
+pub fn update_stats(env: &Env, f: impl FnOnce(&mut ProxyStats)) {
+    let mut stats = get_stats(env);
+    f(&mut stats);
+    env.storage().instance().set(&DataKeyProxy::Stats, &stats);
+}
+
+pub fn get_fees_collected(env: &Env, token: Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKeyProxy::FeesCollected(token))
+        .unwrap_or(0)
+}
+
+pub fn add_fees_collected(env: &Env, token: Address, value: i128) {
+    let total = get_fees_collected(env, token.clone())
+        .checked_add(value)
+        .unwrap();
+    env.storage()
+        .instance()
+        .set(&DataKeyProxy::FeesCollected(token), &total);
+}
+
+pub fn get_backend_stats(env: &Env, backend: Address) -> BackendStats {
+    let key = DataKeyProxy::BackendStats(backend);
+    env.storage().persistent().get(&key).unwrap_or_default()
+}
+
+pub fn add_backend_fulfillment(env: &Env, backend: Address, latency: u32) {
+    let mut stats = get_backend_stats(env, backend.clone());
+    stats.fulfilled += 1;
+    stats.total_latency += latency as u64;
+    stats.average_latency = stats.total_latency / stats.fulfilled;
+    let key = DataKeyProxy::BackendStats(backend);
+    env.storage().persistent().set(&key, &stats);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, STATS_BUMP_AMOUNT, STATS_BUMP_AMOUNT);
+}
+
+pub fn get_dapp_stats(env: &Env, dapp: Address) -> DappStats {
+    let key = DataKeyProxy::DappStats(dapp);
+    env.storage().persistent().get(&key).unwrap_or_default()
+}
+
+pub fn update_dapp_stats(env: &Env, dapp: Address, f: impl FnOnce(&mut DappStats)) {
+    let mut stats = get_dapp_stats(env, dapp.clone());
+    f(&mut stats);
+    let key = DataKeyProxy::DappStats(dapp);
+    env.storage().persistent().set(&key, &stats);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, STATS_BUMP_AMOUNT, STATS_BUMP_AMOUNT);
+}
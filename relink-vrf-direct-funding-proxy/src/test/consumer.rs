@@ -2,15 +2,28 @@ extern crate std;
 
 use std::println;
 
-use relink::testutils::TestOracleGenerator;
+use relink::consumer::{
+    DataKeyConsumer, OracleEntry, SignatureStatus, VerificationError, VerificationPolicy,
+    ED25519_PREVIOUS_EPOCH, ED25519_SIGNATURE,
+};
+use relink::events::{CallbackFailedEvent, CALLBACK_FAILED, EVENT_VERSION};
+use relink::pending_requests;
+use relink::testutils::{
+    active_config_digest, advance_ledger_sequence, TestEd25519Oracle, TestOracleGenerator,
+};
+use relink::{Error, RequestId};
 use soroban_sdk::testutils::{
-    Address as AddressTestTrait, AuthorizedFunction, AuthorizedInvocation,
+    Address as AddressTestTrait, AuthorizedFunction, AuthorizedInvocation, Events,
 };
-use soroban_sdk::{token, vec, Address, BytesN, Env, IntoVal, Symbol};
+use soroban_sdk::{map, token, vec, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, Vec};
 
 use crate::test::test_consumer::{TestConsumer, TestConsumerClient};
-use crate::{RelinkVrfDirectFundingProxy, RelinkVrfDirectFundingProxyClient};
+use crate::{
+    BackendStats, DappStats, ProxyStats, RelinkVrfDirectFundingProxy,
+    RelinkVrfDirectFundingProxyClient,
+};
 
+#[allow(dead_code)]
 struct Setup<'a> {
     env: Env,
     token: token::Client<'a>,
@@ -89,9 +102,9 @@ fn request_randomness() {
         oracle2.address(env),
         oracle3.address(env),
     ];
-    println!("oracle1 address: {}", oracle1.address(&env));
-    println!("oracle2 address: {}", oracle2.address(&env));
-    println!("oracle3 address: {}", oracle3.address(&env));
+    println!("oracle1 address: {}", oracle1.address(env));
+    println!("oracle2 address: {}", oracle2.address(env));
+    println!("oracle3 address: {}", oracle3.address(env));
     consumer.initialize(&proxy.address, &2, &oracles_pub_keys);
 
     // initiate some requests
@@ -115,7 +128,7 @@ fn request_randomness() {
     let random_words = vec![
         env,
         BytesN::from_array(
-            &env,
+            env,
             &[
                 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3,
                 4, 5, 6, 7,
@@ -125,9 +138,9 @@ fn request_randomness() {
     // generate oracle signatures
     let signatures = vec![
         env,
-        oracle1.sign(&env, &consumer.address, &id1, &random_words),
+        oracle1.sign(env, &consumer.address, &id1, &random_words),
         // oracle2.sign(&env, &consumer.address, &id1, &random_words),
-        oracle3.sign(&env, &consumer.address, &id1, &random_words),
+        oracle3.sign(env, &consumer.address, &id1, &random_words),
     ];
 
     // execute the callback with randomness
@@ -146,7 +159,7 @@ fn request_randomness() {
                     // Address of the called contract
                     proxy.address.clone(),
                     // Name of the called function
-                    Symbol::new(&env, "callback_with_randomness"),
+                    Symbol::new(env, "callback_with_randomness"),
                     // Arguments used to call `callback_with_randomness` (converted to the env-managed vector via `into_val`)
                     (backend, id1, random_words, signatures).into_val(env),
                 )),
@@ -156,3 +169,852 @@ fn request_randomness() {
         )]
     );
 }
+
+#[test]
+fn stats() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // generate some key pairs for oracles
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 2);
+    let oracle1 = &oracles[0];
+    let oracle2 = &oracles[1];
+    consumer.initialize(
+        &proxy.address,
+        &2,
+        &vec![env, oracle1.address(env), oracle2.address(env)],
+    );
+
+    // initiate some requests
+    let user = setup.random_account(&50);
+    let id1 = consumer.initiate_randomness_request(&user, &10);
+    let id2 = consumer.initiate_randomness_request(&user, &12);
+    let id3 = consumer.initiate_randomness_request(&user, &10);
+
+    let backend = setup.random_account(&0);
+    proxy.add_backend_whitelist(&backend);
+
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let other_words = vec![env, BytesN::from_array(env, &[8; 32])];
+    let sign = |id: &RequestId, words: &Vec<BytesN<32>>| {
+        vec![
+            env,
+            oracle1.sign(env, &consumer.address, id, words),
+            oracle2.sign(env, &consumer.address, id, words),
+        ]
+    };
+
+    // fulfill the first request after 4 ledgers
+    advance_ledger_sequence(env, 4);
+    proxy.callback_with_randomness(&backend, &id1, &random_words, &sign(&id1, &random_words));
+
+    // a backend tampering with the words makes the callback revert by default
+    assert_eq!(
+        proxy.try_callback_with_randomness(
+            &backend,
+            &id2,
+            &random_words,
+            &sign(&id2, &other_words)
+        ),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    assert_eq!(proxy.stats().failed_callbacks, 0);
+
+    // a backend that opted in gets the failure recorded instead, the request stays pending
+    assert!(!proxy.records_failed_callbacks(&backend));
+    proxy.set_record_failed_callbacks(&backend, &true);
+    assert!(proxy.records_failed_callbacks(&backend));
+    proxy.callback_with_randomness(&backend, &id2, &random_words, &sign(&id2, &other_words));
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == proxy.address)
+        .last()
+        .unwrap();
+    assert_eq!(
+        vec![env, event],
+        vec![
+            env,
+            (
+                proxy.address.clone(),
+                (CALLBACK_FAILED, id2.clone()).into_val(env),
+                CallbackFailedEvent {
+                    version: EVENT_VERSION,
+                    id: id2.clone(),
+                    backend: backend.clone(),
+                }
+                .into_val(env),
+            )
+        ]
+    );
+    // and can still be fulfilled 2 ledgers later
+    advance_ledger_sequence(env, 2);
+    proxy.callback_with_randomness(&backend, &id2, &random_words, &sign(&id2, &random_words));
+
+    // the third request is cancelled by the origin that paid for it, the fee is kept
+    proxy.cancel_request(&id3);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            user.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    proxy.address.clone(),
+                    Symbol::new(env, "cancel_request"),
+                    (id3.clone(),).into_val(env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(setup.token.balance(&proxy.address), 32);
+    assert_eq!(
+        proxy.try_callback_with_randomness(
+            &backend,
+            &id3,
+            &random_words,
+            &sign(&id3, &random_words)
+        ),
+        Err(Ok(Error::RequestUnknown))
+    );
+
+    assert_eq!(
+        proxy.stats(),
+        ProxyStats {
+            total_requests: 3,
+            fulfilled: 2,
+            cancelled: 1,
+            failed_callbacks: 1,
+        }
+    );
+    assert_eq!(proxy.get_fees_collected(&setup.token.address), 32);
+    assert_eq!(
+        proxy.get_backend_stats(&backend),
+        BackendStats {
+            fulfilled: 2,
+            total_latency: 10,
+            average_latency: 5,
+        }
+    );
+    assert_eq!(
+        proxy.get_dapp_stats(&consumer.address),
+        DappStats {
+            requests: 3,
+            fulfilled: 2,
+            cancelled: 1,
+            failed_callbacks: 1,
+        }
+    );
+}
+
+#[test]
+fn config_rotation() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // the consumer starts with the first oracle and rotates to the second one
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 2);
+    let old_oracle = &oracles[0];
+    let new_oracle = &oracles[1];
+    consumer.initialize(&proxy.address, &1, &vec![env, old_oracle.address(env)]);
+    let old_digest = active_config_digest(env, &consumer.address);
+    consumer.set_overlap_window(&10);
+    let activation = env.ledger().sequence() + 5;
+    assert_eq!(
+        consumer.set_oracle_config(&vec![env, new_oracle.address(env)], &1, &activation),
+        2
+    );
+
+    let user = setup.random_account(&50);
+    let backend = setup.random_account(&0);
+    proxy.add_backend_whitelist(&backend);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    // sign with the old oracle for the configuration of the first epoch
+    let sign_old = |id: &RequestId| {
+        let tx_input_hash = relink::consumer::tx_input_hash(env, id, &random_words, &old_digest);
+        vec![
+            env,
+            old_oracle.sign_typed(env, &consumer.address, &tx_input_hash),
+        ]
+    };
+
+    // the new oracle is not accepted before the activation ledger
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let signatures = vec![
+        env,
+        new_oracle.sign(env, &consumer.address, &id, &random_words),
+    ];
+    assert_eq!(
+        proxy.try_callback_with_randomness(&backend, &id, &random_words, &signatures),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    proxy.callback_with_randomness(&backend, &id, &random_words, &sign_old(&id));
+    assert_eq!(proxy.stats().fulfilled, 1);
+
+    // after the activation both configurations are accepted during the overlap window
+    advance_ledger_sequence(env, 5);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let signatures = vec![
+        env,
+        new_oracle.sign(env, &consumer.address, &id, &random_words),
+    ];
+    proxy.callback_with_randomness(&backend, &id, &random_words, &signatures);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    proxy.callback_with_randomness(&backend, &id, &random_words, &sign_old(&id));
+    assert_eq!(proxy.stats().fulfilled, 3);
+
+    // the old configuration expires with the overlap window
+    advance_ledger_sequence(env, 10);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    assert_eq!(
+        proxy.try_callback_with_randomness(&backend, &id, &random_words, &sign_old(&id)),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    assert_eq!(proxy.stats().fulfilled, 3);
+}
+
+#[test]
+fn weighted_oracles() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // a trusted oracle outweighs two partner oracles
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 3);
+    consumer.initialize(&proxy.address, &1, &vec![env, oracles[0].address(env)]);
+    let entry = |index: usize, weight: u32| OracleEntry {
+        key: oracles[index].address(env).into(),
+        weight,
+        groups: Vec::new(env),
+    };
+    consumer.set_weighted_oracle_config(
+        &vec![env, entry(0, 1), entry(1, 2), entry(2, 1)],
+        &2,
+        &Map::new(env),
+        &env.ledger().sequence(),
+    );
+
+    let user = setup.random_account(&50);
+    let backend = setup.random_account(&0);
+    proxy.add_backend_whitelist(&backend);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let sign = |id: &RequestId, signers: &[usize]| {
+        let mut signatures = Vec::new(env);
+        for index in signers {
+            signatures.push_back(oracles[*index].sign(env, &consumer.address, id, &random_words));
+        }
+        signatures
+    };
+
+    // a single partner oracle is not enough
+    let id = consumer.initiate_randomness_request(&user, &10);
+    assert_eq!(
+        proxy.try_callback_with_randomness(&backend, &id, &random_words, &sign(&id, &[0])),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    // the trusted oracle alone reaches the threshold
+    proxy.callback_with_randomness(&backend, &id, &random_words, &sign(&id, &[1]));
+    // as do both partner oracles
+    let id = consumer.initiate_randomness_request(&user, &10);
+    proxy.callback_with_randomness(&backend, &id, &random_words, &sign(&id, &[0, 2]));
+    assert_eq!(proxy.stats().fulfilled, 2);
+}
+
+#[test]
+fn group_quorum() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // two relink nodes and one partner node, requiring one of each
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 3);
+    consumer.initialize(&proxy.address, &1, &vec![env, oracles[0].address(env)]);
+    let relink = Symbol::new(env, "relink");
+    let partner = Symbol::new(env, "partner");
+    let entry = |index: usize, group: &Symbol| OracleEntry {
+        key: oracles[index].address(env).into(),
+        weight: 1,
+        groups: vec![env, group.clone()],
+    };
+    consumer.set_weighted_oracle_config(
+        &vec![
+            env,
+            entry(0, &relink),
+            entry(1, &relink),
+            entry(2, &partner),
+        ],
+        &2,
+        &map![env, (relink.clone(), 1), (partner.clone(), 1)],
+        &env.ledger().sequence(),
+    );
+
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let sign = |signers: &[usize]| {
+        let mut signatures = Vec::new(env);
+        for index in signers {
+            signatures.push_back(oracles[*index].sign(env, &consumer.address, &id, &random_words));
+        }
+        signatures
+    };
+
+    // two relink nodes reach the threshold but not the partner minimum
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&id, &random_words, &sign(&[0, 1])),
+        Err(Ok(Error::GroupQuorumNotMet))
+    );
+    // the detailed error names the group
+    let signatures = sign(&[0, 1]);
+    let result = env.as_contract(&consumer.address, || {
+        relink::consumer::check_signatures(
+            env,
+            |config_digest| relink::consumer::tx_input_hash(env, &id, &random_words, config_digest),
+            &signatures,
+        )
+    });
+    assert_eq!(result, Err(VerificationError::GroupQuorumNotMet(partner)));
+    // one node of each group satisfies the policy
+    consumer.verify_and_fulfill_randomness(&id, &random_words, &sign(&[0, 2]));
+}
+
+#[test]
+fn ed25519_oracles() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // mix a secp256k1 and an ed25519 oracle
+    let secp_oracle = &TestOracleGenerator::new().generate_sorted(env, 1)[0];
+    let ed_oracle = TestEd25519Oracle::new(&[1; 32]);
+    consumer.initialize(&proxy.address, &1, &vec![env, secp_oracle.address(env)]);
+    consumer.set_weighted_oracle_config(
+        &vec![
+            env,
+            OracleEntry::new(env, secp_oracle.address(env)),
+            OracleEntry::new(env, ed_oracle.key(env)),
+        ],
+        &2,
+        &Map::new(env),
+        &env.ledger().sequence(),
+    );
+
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let secp_signature = secp_oracle.sign(env, &consumer.address, &id, &random_words);
+
+    // the ed25519 signature has to reference the oracle's index
+    let signatures = vec![
+        env,
+        secp_signature.clone(),
+        ed_oracle.sign(env, &consumer.address, 0, &id, &random_words),
+    ];
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&id, &random_words, &signatures),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    // an oracle cannot sign twice
+    let ed_signature = ed_oracle.sign(env, &consumer.address, 1, &id, &random_words);
+    let signatures = vec![env, ed_signature.clone(), ed_signature.clone()];
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&id, &random_words, &signatures),
+        Err(Ok(Error::UnorderedOracles))
+    );
+    // both schemes count towards the threshold
+    let signatures = vec![env, ed_signature, secp_signature];
+    consumer.verify_and_fulfill_randomness(&id, &random_words, &signatures);
+}
+
+#[test]
+fn indexed_signatures() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // three secp256k1 oracles and an ed25519 oracle, any three have to sign
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 3);
+    let ed_oracle = TestEd25519Oracle::new(&[1; 32]);
+    let addresses: Vec<_> = vec![
+        env,
+        oracles[0].address(env),
+        oracles[1].address(env),
+        oracles[2].address(env),
+    ];
+    consumer.initialize(&proxy.address, &1, &addresses);
+    let mut entries: Vec<OracleEntry> = Vec::new(env);
+    for address in addresses.iter() {
+        entries.push_back(OracleEntry::new(env, address));
+    }
+    entries.push_back(OracleEntry::new(env, ed_oracle.key(env)));
+    consumer.set_weighted_oracle_config(&entries, &3, &Map::new(env), &env.ledger().sequence());
+
+    let backend = Address::generate(env);
+    proxy.add_backend_whitelist(&backend);
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let sign = |index: usize| oracles[index].sign(env, &consumer.address, &id, &random_words);
+    let ed_signature = ed_oracle.sign(env, &consumer.address, 3, &id, &random_words);
+
+    // the bitmap must match the number of signatures and the oracle list
+    let signatures = vec![env, sign(0), sign(2), ed_signature.clone()];
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(&id, &random_words, &0b101, &signatures),
+        Err(Ok(Error::InvalidSignerBitmap))
+    );
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(&id, &random_words, &0b10101, &signatures),
+        Err(Ok(Error::InvalidSignerBitmap))
+    );
+    // a signature has to come from the oracle at its index
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(&id, &random_words, &0b1011, &signatures),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+    // too few signers
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(
+            &id,
+            &random_words,
+            &0b1001,
+            &vec![env, sign(0), ed_signature.clone()]
+        ),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+
+    // the proxy forwards the indexed format
+    proxy.callback_with_randomness_indexed(&backend, &id, &random_words, &0b1101, &signatures);
+    assert_eq!(proxy.stats().fulfilled, 1);
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(&id, &random_words, &0b1101, &signatures),
+        Err(Ok(Error::RequestUnknown))
+    );
+}
+
+#[test]
+fn ed25519_config_rotation() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    // start with a single ed25519 oracle, then add a second one
+    let secp_oracle = &TestOracleGenerator::new().generate_sorted(env, 1)[0];
+    let ed_oracle = TestEd25519Oracle::new(&[1; 32]);
+    let other_ed_oracle = TestEd25519Oracle::new(&[2; 32]);
+    consumer.initialize(&proxy.address, &1, &vec![env, secp_oracle.address(env)]);
+    consumer.set_overlap_window(&10);
+    consumer.set_weighted_oracle_config(
+        &vec![env, OracleEntry::new(env, ed_oracle.key(env))],
+        &1,
+        &Map::new(env),
+        &env.ledger().sequence(),
+    );
+    let old_digest = active_config_digest(env, &consumer.address);
+    advance_ledger_sequence(env, 10);
+    consumer.set_weighted_oracle_config(
+        &vec![
+            env,
+            OracleEntry::new(env, ed_oracle.key(env)),
+            OracleEntry::new(env, other_ed_oracle.key(env)),
+        ],
+        &1,
+        &Map::new(env),
+        &env.ledger().sequence(),
+    );
+
+    let user = setup.random_account(&50);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    // sign for the previous configuration, flagged as such
+    let sign_old = |id: &RequestId| {
+        let tx_input_hash = relink::consumer::tx_input_hash(env, id, &random_words, &old_digest);
+        let (signature, flags) = ed_oracle.sign_typed(env, &consumer.address, 0, &tx_input_hash);
+        vec![env, (signature, flags | ED25519_PREVIOUS_EPOCH)]
+    };
+
+    // during the overlap window responses signed before the rotation are accepted
+    let id = consumer.initiate_randomness_request(&user, &10);
+    consumer.verify_and_fulfill_randomness(&id, &random_words, &sign_old(&id));
+    let id = consumer.initiate_randomness_request(&user, &10);
+    consumer.verify_and_fulfill_indexed(&id, &random_words, &0b1, &sign_old(&id));
+    // as well as responses for the new configuration
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let signatures = vec![
+        env,
+        ed_oracle.sign(env, &consumer.address, 0, &id, &random_words),
+    ];
+    consumer.verify_and_fulfill_randomness(&id, &random_words, &signatures);
+
+    // all ed25519 signatures of a response have to be made for the same configuration
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let mut signatures = sign_old(&id);
+    signatures.push_back(other_ed_oracle.sign(env, &consumer.address, 1, &id, &random_words));
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&id, &random_words, &signatures),
+        Err(Ok(Error::InvalidSignature))
+    );
+    // a secp256k1 signature in the slot of an ed25519 oracle is rejected
+    let signatures = vec![
+        env,
+        secp_oracle.sign(env, &consumer.address, &id, &random_words),
+    ];
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(&id, &random_words, &0b1, &signatures),
+        Err(Ok(Error::InvalidSignature))
+    );
+
+    // after the overlap window the previous configuration is rejected without verifying
+    advance_ledger_sequence(env, 10);
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&id, &random_words, &sign_old(&id)),
+        Err(Ok(Error::UnauthorizedOracleSignatures))
+    );
+}
+
+#[test]
+fn request_id_binding() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    let oracle = &TestOracleGenerator::new().generate_sorted(env, 1)[0];
+    consumer.initialize(&proxy.address, &1, &vec![env, oracle.address(env)]);
+    let backend = Address::generate(env);
+    proxy.add_backend_whitelist(&backend);
+
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let signatures = vec![env, oracle.sign(env, &consumer.address, &id, &random_words)];
+
+    // the id has to be derived from the origin and nonce of the request
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness_v2(
+            &id,
+            &user,
+            &1,
+            &backend,
+            &random_words,
+            &signatures
+        ),
+        Err(Ok(Error::RequestIdMismatch))
+    );
+    let other = Address::generate(env);
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness_v2(
+            &id,
+            &other,
+            &0,
+            &backend,
+            &random_words,
+            &signatures
+        ),
+        Err(Ok(Error::RequestIdMismatch))
+    );
+
+    // the proxy passes the origin and nonce it derived the id from
+    proxy.callback_with_randomness_v2(&backend, &id, &random_words, &signatures);
+    assert_eq!(proxy.stats().fulfilled, 1);
+    assert_eq!(proxy.stats().failed_callbacks, 0);
+}
+
+#[test]
+fn raw_signatures() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    let oracle = &TestOracleGenerator::new().generate_sorted(env, 1)[0];
+    consumer.initialize(&proxy.address, &1, &vec![env, oracle.address(env)]);
+    let backend = Address::generate(env);
+    proxy.add_backend_whitelist(&backend);
+    let user = setup.random_account(&50);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+
+    // 65 byte r || s || v with an Ethereum style v
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let (signature, recovery_id) = oracle.sign(env, &consumer.address, &id, &random_words);
+    let mut raw = Bytes::from_array(env, &signature.to_array());
+    raw.push_back(27 + recovery_id as u8);
+    proxy.callback_with_randomness_raw(&backend, &id, &random_words, &vec![env, raw]);
+    assert_eq!(proxy.stats().fulfilled, 1);
+
+    // 64 byte compact r || yParityAndS
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let (signature, recovery_id) = oracle.sign(env, &consumer.address, &id, &random_words);
+    let mut compact = signature.to_array();
+    compact[32] |= (recovery_id as u8) << 7;
+    let compact = Bytes::from_array(env, &compact);
+    proxy.callback_with_randomness_raw(&backend, &id, &random_words, &vec![env, compact]);
+    assert_eq!(proxy.stats().fulfilled, 2);
+
+    // other lengths are rejected
+    let id = consumer.initiate_randomness_request(&user, &10);
+    assert_eq!(
+        proxy.try_callback_with_randomness_raw(
+            &backend,
+            &id,
+            &random_words,
+            &vec![env, Bytes::from_array(env, &[0; 63])]
+        ),
+        Err(Ok(Error::InvalidSignature))
+    );
+}
+
+#[test]
+fn response_deadline() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    let oracle = &TestOracleGenerator::new().generate_sorted(env, 1)[0];
+    consumer.initialize(&proxy.address, &1, &vec![env, oracle.address(env)]);
+    // the ledger of a request isn't kept longer than the request itself
+    assert_eq!(
+        consumer.try_set_max_fulfillment_delay(&(pending_requests::REQUEST_TTL + 1)),
+        Err(Ok(Error::InvalidFulfillmentDelay))
+    );
+    consumer.set_max_fulfillment_delay(&10);
+    let backend = Address::generate(env);
+    proxy.add_backend_whitelist(&backend);
+
+    let user = setup.random_account(&50);
+    let first = consumer.initiate_randomness_request(&user, &10);
+    let second = consumer.initiate_randomness_request(&user, &10);
+    let third = consumer.initiate_randomness_request(&user, &10);
+    let deadline = env.ledger().sequence() + 10;
+    let expiry = env.as_contract(&consumer.address, || {
+        pending_requests::expiry(env, first.clone())
+    });
+    assert_eq!(expiry, Some(deadline));
+
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let sign = |id: &RequestId| vec![env, oracle.sign(env, &consumer.address, id, &random_words)];
+
+    // without the ledger of the request its deadline is unknown and the response is rejected
+    env.as_contract(&consumer.address, || {
+        let key = DataKeyConsumer::RequestLedger(third.clone());
+        env.storage().temporary().remove(&key);
+    });
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&third, &random_words, &sign(&third)),
+        Err(Ok(Error::ResponseExpired))
+    );
+
+    // a response is accepted up to the deadline, after which the ledger of the request is dropped
+    advance_ledger_sequence(env, 10);
+    consumer.verify_and_fulfill_randomness(&first, &random_words, &sign(&first));
+    let ledger = env.as_contract(&consumer.address, || {
+        relink::consumer::get_request_ledger(env, &first)
+    });
+    assert_eq!(ledger, None);
+
+    // late responses are rejected and the request stays pending at the proxy
+    advance_ledger_sequence(env, 1);
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&second, &random_words, &sign(&second)),
+        Err(Ok(Error::ResponseExpired))
+    );
+    assert_eq!(
+        proxy.try_callback_with_randomness(&backend, &second, &random_words, &sign(&second)),
+        Err(Ok(Error::ResponseExpired))
+    );
+    assert_eq!(proxy.stats().failed_callbacks, 0);
+}
+
+#[test]
+fn verification_policy() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 3);
+    let mut addresses = Vec::new(env);
+    for oracle in oracles.iter() {
+        addresses.push_back(oracle.address(env));
+    }
+    consumer.initialize(&proxy.address, &1, &addresses);
+
+    // a jackpot needs two signatures, one of them from the third oracle
+    let user = setup.random_account(&50);
+    let policy = VerificationPolicy {
+        min_threshold: 2,
+        required_oracles: vec![env, oracles[2].address(env).into()],
+    };
+    let jackpot = consumer.initiate_request_with_policy(&user, &10, &policy);
+    let indexed = consumer.initiate_request_with_policy(&user, &10, &policy);
+    let routine = consumer.initiate_randomness_request(&user, &10);
+
+    // policies the configuration can't satisfy are rejected when the request is made
+    let unreachable = VerificationPolicy {
+        min_threshold: 4,
+        required_oracles: Vec::new(env),
+    };
+    assert_eq!(
+        consumer.try_initiate_request_with_policy(&user, &10, &unreachable),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    let outsider = VerificationPolicy {
+        min_threshold: 0,
+        required_oracles: vec![env, TestEd25519Oracle::new(&[9; 32]).key(env)],
+    };
+    assert_eq!(
+        consumer.try_initiate_request_with_policy(&user, &10, &outsider),
+        Err(Ok(Error::OracleUnknown))
+    );
+
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let sign = |id: &RequestId, indexes: &[usize]| {
+        let mut signatures = Vec::new(env);
+        for index in indexes {
+            signatures.push_back(oracles[*index].sign(env, &consumer.address, id, &random_words));
+        }
+        signatures
+    };
+
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&jackpot, &random_words, &sign(&jackpot, &[0])),
+        Err(Ok(Error::TooFewSignatures))
+    );
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(
+            &jackpot,
+            &random_words,
+            &sign(&jackpot, &[0, 1])
+        ),
+        Err(Ok(Error::RequiredOracleMissing))
+    );
+    consumer.verify_and_fulfill_randomness(&jackpot, &random_words, &sign(&jackpot, &[0, 2]));
+
+    // the indexed format is held to the same policy
+    let backend = Address::generate(env);
+    proxy.add_backend_whitelist(&backend);
+    assert_eq!(
+        consumer.try_verify_and_fulfill_indexed(
+            &indexed,
+            &random_words,
+            &0b11,
+            &sign(&indexed, &[0, 1])
+        ),
+        Err(Ok(Error::RequiredOracleMissing))
+    );
+    assert_eq!(
+        proxy.try_callback_with_randomness_indexed(
+            &backend,
+            &indexed,
+            &random_words,
+            &0b11,
+            &sign(&indexed, &[0, 1]),
+        ),
+        Err(Ok(Error::RequiredOracleMissing))
+    );
+    proxy.callback_with_randomness_indexed(
+        &backend,
+        &indexed,
+        &random_words,
+        &0b101,
+        &sign(&indexed, &[0, 2]),
+    );
+    assert_eq!(proxy.stats().fulfilled, 1);
+
+    // routine requests keep using the configured threshold
+    consumer.verify_and_fulfill_randomness(&routine, &random_words, &sign(&routine, &[0]));
+}
+
+#[test]
+fn diagnose_signatures() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+    let consumer = &setup.consumer;
+    let env = &setup.env;
+
+    let oracles = TestOracleGenerator::new().generate_sorted(env, 3);
+    consumer.initialize(
+        &proxy.address,
+        &2,
+        &vec![env, oracles[0].address(env), oracles[1].address(env)],
+    );
+    let user = setup.random_account(&50);
+    let id = consumer.initiate_randomness_request(&user, &10);
+    let random_words = vec![env, BytesN::from_array(env, &[7; 32])];
+    let sign = |index: usize| oracles[index].sign(env, &consumer.address, &id, &random_words);
+
+    // unordered, non-member and malformed signatures
+    let signatures = vec![
+        env,
+        sign(1),
+        sign(0),
+        sign(2),
+        (BytesN::from_array(env, &[0; 64]), 0),
+    ];
+    let diagnostics = consumer.diagnose_signatures(&id, &random_words, &signatures);
+    assert_eq!(diagnostics.epoch, 1);
+    assert_eq!(
+        diagnostics.config_digest,
+        active_config_digest(env, &consumer.address)
+    );
+    assert_eq!(diagnostics.weight, 1);
+    assert_eq!(diagnostics.threshold, 2);
+    let outcomes: std::vec::Vec<_> = diagnostics
+        .signatures
+        .iter()
+        .map(|diagnostic| (diagnostic.status, diagnostic.member, diagnostic.ordered))
+        .collect();
+    assert_eq!(
+        outcomes,
+        std::vec![
+            (SignatureStatus::Valid, true, true),
+            (SignatureStatus::Valid, true, false),
+            (SignatureStatus::Valid, false, true),
+            (SignatureStatus::Invalid, false, false),
+        ]
+    );
+    assert_eq!(
+        diagnostics.signatures.get_unchecked(1).signer,
+        oracles[0].address(env).into()
+    );
+    // the dry run does not change the outcome of the verification
+    assert_eq!(
+        consumer.try_verify_and_fulfill_randomness(&id, &random_words, &signatures),
+        Err(Ok(Error::UnorderedOracles))
+    );
+
+    // ed25519 signatures, also with an unknown index, are reported without counting their weight
+    let ed_oracle = TestEd25519Oracle::new(&[1; 32]);
+    let mut entries: Vec<OracleEntry> = Vec::new(env);
+    entries.push_back(OracleEntry::new(env, oracles[0].address(env)));
+    entries.push_back(OracleEntry::new(env, ed_oracle.key(env)));
+    consumer.set_weighted_oracle_config(&entries, &2, &Map::new(env), &env.ledger().sequence());
+    let forged = (BytesN::from_array(env, &[1; 64]), ED25519_SIGNATURE | 1);
+    let unknown = (BytesN::from_array(env, &[1; 64]), ED25519_SIGNATURE | 5);
+    let signatures = vec![env, sign(0), forged, unknown];
+    let diagnostics = consumer.diagnose_signatures(&id, &random_words, &signatures);
+    assert_eq!(diagnostics.weight, 1);
+    let outcomes: std::vec::Vec<_> = diagnostics
+        .signatures
+        .iter()
+        .map(|diagnostic| (diagnostic.status, diagnostic.member, diagnostic.ordered))
+        .collect();
+    assert_eq!(
+        outcomes,
+        std::vec![
+            (SignatureStatus::Valid, true, true),
+            (SignatureStatus::Unverified, true, true),
+            (SignatureStatus::Unverified, false, true),
+        ]
+    );
+}
@@ -3,16 +3,17 @@ extern crate std;
 use std::println;
 
 use soroban_sdk::{
-    symbol_short,
     testutils::{Address as AddressTestTrait, Events},
     token, vec, Address, Env, IntoVal, Vec,
 };
 
-use relink::RequestId;
-
-use crate::events::RandomnessRequestData;
 use crate::{RelinkVrfDirectFundingProxy, RelinkVrfDirectFundingProxyClient};
+use relink::events::{
+    RandomnessRequestedEvent, WithdrawnEvent, EVENT_VERSION, RANDOMNESS_REQUESTED, WITHDRAWN,
+};
+use relink::{Error, RequestId};
 
+#[allow(dead_code)]
 struct Setup<'a> {
     env: Env,
     token: token::Client<'a>,
@@ -129,8 +130,18 @@ fn request_event() {
             env,
             (
                 setup.proxy.address.clone(),
-                (symbol_short!("request"), origin, dapp, expected_nonce).into_val(env),
-                RandomnessRequestData {
+                (
+                    RANDOMNESS_REQUESTED,
+                    origin.clone(),
+                    dapp.clone(),
+                    expected_nonce
+                )
+                    .into_val(env),
+                RandomnessRequestedEvent {
+                    version: EVENT_VERSION,
+                    origin,
+                    dapp,
+                    nonce: expected_nonce,
                     id: expected_request_id,
                     request_confirmations: 2,
                     num_words: 1
@@ -185,3 +196,60 @@ fn max_words() {
 
     proxy.request_randomness(&origin, &10, &dapp, &2, &11);
 }
+
+#[test]
+fn withdraw_event() {
+    let setup = Setup::new();
+    let env = &setup.env;
+    let proxy = &setup.proxy;
+
+    let origin = setup.random_account(&50);
+    let dapp = setup.random_account(&0);
+    proxy.request_randomness(&origin, &10, &dapp, &2, &1);
+    proxy.withdraw(&setup.token.address);
+
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == proxy.address)
+        .last()
+        .unwrap();
+    assert_eq!(
+        vec![env, event],
+        vec![
+            env,
+            (
+                proxy.address.clone(),
+                (WITHDRAWN, setup.token.address.clone()).into_val(env),
+                WithdrawnEvent {
+                    version: EVENT_VERSION,
+                    token: setup.token.address.clone(),
+                    to: setup.proxy_owner.clone(),
+                    amount: 10,
+                }
+                .into_val(env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn pause() {
+    let setup = Setup::new();
+    let proxy = &setup.proxy;
+
+    let origin = setup.random_account(&50);
+    let dapp = setup.random_account(&0);
+
+    proxy.pause();
+    assert!(proxy.is_paused());
+    assert_eq!(
+        proxy.try_request_randomness(&origin, &10, &dapp, &2, &1),
+        Err(Ok(Error::Paused))
+    );
+
+    proxy.unpause();
+    assert!(!proxy.is_paused());
+    proxy.request_randomness(&origin, &10, &dapp, &2, &1);
+}
@@ -1,37 +1,12 @@
 extern crate std;
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, Symbol, Vec};
 
-use relink::{consumer, Error, EthAddress, RequestId, VrfDirectFundingConsumer};
-
-const REQUEST_BUMP_AMOUNT: u32 = 34560; // 2 days
-
-#[derive(Clone)]
-#[contracttype]
-pub enum DataKey {
-    Request(RequestId),
-}
-
-pub fn add_request_id(env: &Env, id: RequestId) {
-    let key = DataKey::Request(id);
-    env.storage().temporary().set(&key, &());
-    env.storage()
-        .temporary()
-        .extend_ttl(&key, REQUEST_BUMP_AMOUNT, REQUEST_BUMP_AMOUNT);
-}
-
-pub fn has_request_id(env: &Env, id: RequestId) -> Result<(), Error> {
-    let key = DataKey::Request(id);
-    env.storage()
-        .temporary()
-        .get(&key)
-        .ok_or(Error::RequestUnknown)
-}
-
-pub fn remove_request_id(env: &Env, id: RequestId) {
-    let key = DataKey::Request(id);
-    env.storage().temporary().remove(&key);
-}
+use relink::consumer::{OracleEntry, SignatureDiagnostics, VerificationPolicy};
+use relink::{
+    consumer, pending_requests, Error, EthAddress, RequestId, VrfDirectFundingConsumer,
+    VrfDirectFundingConsumerV2, VrfDirectFundingIndexedConsumer,
+};
 
 #[contract]
 pub struct TestConsumer;
@@ -39,17 +14,83 @@ pub struct TestConsumer;
 #[contractimpl]
 impl TestConsumer {
     /// Initialize contract by setting the proxy address and trusted oracles.
-    pub fn initialize(env: Env, proxy: Address, threshold: u32, oracles: Vec<EthAddress>) {
-        consumer::init(&env, &proxy, threshold, oracles);
+    pub fn initialize(
+        env: Env,
+        proxy: Address,
+        threshold: u32,
+        oracles: Vec<EthAddress>,
+    ) -> Result<(), Error> {
+        consumer::init(&env, &proxy, threshold, oracles, None)
+    }
+
+    /// Schedule a new oracle configuration.
+    pub fn set_oracle_config(
+        env: Env,
+        oracles: Vec<EthAddress>,
+        threshold: u32,
+        activation_ledger: u32,
+    ) -> Result<u32, Error> {
+        consumer::set_oracle_config(&env, oracles, threshold, activation_ledger)
+    }
+
+    /// Schedule a new configuration with weighted oracles.
+    pub fn set_weighted_oracle_config(
+        env: Env,
+        oracles: Vec<OracleEntry>,
+        threshold: u32,
+        group_minimums: Map<Symbol, u32>,
+        activation_ledger: u32,
+    ) -> Result<u32, Error> {
+        consumer::set_weighted_oracle_config(
+            &env,
+            oracles,
+            threshold,
+            group_minimums,
+            activation_ledger,
+        )
+    }
+
+    /// Set the number of ledgers the previous configuration is accepted after a rotation.
+    pub fn set_overlap_window(env: Env, ledgers: u32) {
+        consumer::set_overlap_window(&env, ledgers);
+    }
+
+    /// Set the number of ledgers after a request during which its response is accepted.
+    pub fn set_max_fulfillment_delay(env: Env, ledgers: u32) -> Result<(), Error> {
+        consumer::set_max_fulfillment_delay(&env, ledgers)
+    }
+
+    /// Dry run the verification of a response, reporting the outcome of every signature.
+    pub fn diagnose_signatures(
+        env: Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> SignatureDiagnostics {
+        consumer::diagnose_signatures(&env, &id, &random_words, &signatures)
     }
 
     /// Initiate a request for randomness.
     pub fn initiate_randomness_request(env: Env, origin: Address, value: i128) -> RequestId {
         let id = consumer::request_randomness(&env, origin, value, None, None);
         // store the request id until the response is processed
-        add_request_id(&env, id.clone());
+        pending_requests::add(&env, id.clone());
         id
     }
+
+    /// Initiate a request for randomness whose response has to satisfy the policy.
+    pub fn initiate_request_with_policy(
+        env: Env,
+        origin: Address,
+        value: i128,
+        policy: VerificationPolicy,
+    ) -> Result<RequestId, Error> {
+        consumer::validate_policy(&env, &policy)?;
+        let id = consumer::request_randomness(&env, origin, value, None, None);
+        // store the request id and its policy until the response is processed
+        pending_requests::add_with_policy(&env, id.clone(), &policy);
+        Ok(id)
+    }
 }
 
 #[contractimpl]
@@ -62,11 +103,61 @@ impl VrfDirectFundingConsumer for TestConsumer {
         signatures: Vec<(BytesN<64>, u32)>,
     ) -> Result<(), Error> {
         // check if RequestId exists
-        has_request_id(&env, id.clone())?;
+        let policy = pending_requests::policy(&env, id.clone())?;
+        // verify signatures
+        consumer::verify_randomness_with_policy(&env, &id, &random_words, &signatures, &policy)?;
+        // remove request as it should only be handled once
+        pending_requests::remove(&env, id.clone());
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl VrfDirectFundingConsumerV2 for TestConsumer {
+    /// Process the response to a randomness request without looking up the request id.
+    fn verify_and_fulfill_randomness_v2(
+        env: Env,
+        id: RequestId,
+        origin: Address,
+        nonce: u128,
+        _backend: Address,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        // the request id is bound to this consumer instead, the policy of a pending request
+        // still applies
+        consumer::verify_request_id(&env, &id, &origin, nonce)?;
+        let policy = pending_requests::policy(&env, id.clone())
+            .unwrap_or_else(|_| VerificationPolicy::new(&env));
+        consumer::verify_randomness_with_policy(&env, &id, &random_words, &signatures, &policy)?;
+        pending_requests::remove(&env, id);
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl VrfDirectFundingIndexedConsumer for TestConsumer {
+    /// Process the response to a randomness request in the indexed signature format.
+    fn verify_and_fulfill_indexed(
+        env: Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signer_bitmap: u128,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error> {
+        // check if RequestId exists
+        let policy = pending_requests::policy(&env, id.clone())?;
         // verify signatures
-        consumer::verify_randomness(&env, &id, &random_words, &signatures)?;
+        consumer::verify_randomness_indexed_with_policy(
+            &env,
+            &id,
+            &random_words,
+            signer_bitmap,
+            &signatures,
+            &policy,
+        )?;
         // remove request as it should only be handled once
-        remove_request_id(&env, id.clone());
+        pending_requests::remove(&env, id.clone());
         Ok(())
     }
 }
@@ -48,7 +48,7 @@ fn read_pending_owner(env: &Env) -> Address {
 }
 
 pub fn init(env: &Env, owner: &Address) {
-    if has_owner(&env) {
+    if has_owner(env) {
         panic!("already initialized")
     }
     write_owner(env, owner);
@@ -65,17 +65,17 @@ pub fn owner(env: &Env) -> Address {
 
 pub fn transfer_ownership(env: &Env, to: &Address) {
     require_owner(env);
-    write_pending_owner(&env, &to);
+    write_pending_owner(env, to);
     events::ownership_transfer_requested(env, &read_owner(env), to);
 }
 
 pub fn accept_ownership(env: &Env) {
-    let previous_owner = read_owner(&env);
-    let new_owner = read_pending_owner(&env);
+    let previous_owner = read_owner(env);
+    let new_owner = read_pending_owner(env);
     // only the pending owner can call this
     new_owner.require_auth();
     // update owner
-    write_owner(&env, &new_owner);
+    write_owner(env, &new_owner);
     events::ownership_transferred(env, &previous_owner, &new_owner);
 }
 
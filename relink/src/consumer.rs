@@ -1,6 +1,11 @@
-use soroban_sdk::{bytes, contracttype, Address, Bytes, BytesN, Env, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    bytesn, contracttype, log, vec, Address, BytesN, Env, IntoVal, Map, String, Symbol, Vec,
+};
 
-use crate::utils::address_bytes;
+use crate::eip712::{recover_ordered_signer, recover_signer, typed_data_digest, Domain, Encoder};
+use crate::pending_requests::REQUEST_TTL;
 use crate::{events, Error, EthAddress, RequestId, VrfDirectFundingProxyClient};
 
 #[derive(Clone)]
@@ -8,21 +13,220 @@ use crate::{events, Error, EthAddress, RequestId, VrfDirectFundingProxyClient};
 pub enum DataKeyConsumer {
     DomainSeparator,
     Proxy,
-    Threshold,
-    Oracle(EthAddress),
+    ConfigEpoch,
+    Config(u32),
+    OverlapWindow,
+    InsecureMode,
+    MaxFulfillmentDelay,
+    RequestLedger(RequestId),
+    ConfigUpdateNonce,
+    Domain,
 }
 
-pub fn init(env: &Env, proxy: &Address, threshold: u32, oracles: Vec<EthAddress>) {
-    if has_proxy(&env) {
+/// Flag in the second element of a signature tuple marking an ed25519 signature, the lower 30
+/// bits are the index of the signing oracle in the configuration. Without the flag the element is
+/// the recovery id of a secp256k1 signature.
+pub const ED25519_SIGNATURE: u32 = 1 << 31;
+
+/// Flag next to `ED25519_SIGNATURE` marking an ed25519 signature over the digest of the previous
+/// configuration, for responses signed before a rotation. Unlike secp256k1 signatures, ed25519
+/// signatures cannot be tried against both digests since an invalid one traps the host, so the
+/// flag selects the single configuration all signatures of a response are verified against.
+pub const ED25519_PREVIOUS_EPOCH: u32 = 1 << 30;
+
+/// Number of ledgers the previous configuration stays valid after a scheduled rotation was
+/// activated, unless the owner sets another window. Zero, so that a weaker configuration is never
+/// accepted by default; owners can open a window to cover responses signed before a rotation.
+pub const DEFAULT_OVERLAP_WINDOW: u32 = 0;
+
+/// Identifies an oracle by the key of its signature scheme.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum OracleKey {
+    /// Address of a secp256k1 key, recovered from the signature.
+    Secp256k1(EthAddress),
+    /// Ed25519 public key, the signature is verified against it.
+    Ed25519(BytesN<32>),
+}
+
+impl From<EthAddress> for OracleKey {
+    fn from(address: EthAddress) -> Self {
+        OracleKey::Secp256k1(address)
+    }
+}
+
+/// A permitted oracle, the weight of its signature and the labels of the groups it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleEntry {
+    pub key: OracleKey,
+    pub weight: u32,
+    pub groups: Vec<Symbol>,
+}
+
+impl OracleEntry {
+    /// Create an entry with the default weight of 1 and no groups.
+    pub fn new(env: &Env, key: impl Into<OracleKey>) -> Self {
+        Self {
+            key: key.into(),
+            weight: 1,
+            groups: Vec::new(env),
+        }
+    }
+}
+
+/// A numbered configuration of the permitted oracles.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleConfig {
+    pub oracles: Vec<OracleEntry>,
+    /// Sum of the weights of the valid signatures required.
+    pub threshold: u32,
+    /// Number of oracles of a group which have to sign, in addition to the threshold.
+    pub group_minimums: Map<Symbol, u32>,
+    /// Ledger sequence from which on the configuration is used for verification.
+    pub activation_ledger: u32,
+}
+
+/// Additional requirements for the signatures of a single request.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct VerificationPolicy {
+    /// Minimum total weight of the signers, the threshold of the configuration applies if it is
+    /// higher.
+    pub min_threshold: u32,
+    /// Oracles which have to be among the signers.
+    pub required_oracles: Vec<OracleKey>,
+}
+
+impl VerificationPolicy {
+    /// Policy without additional requirements.
+    pub fn new(env: &Env) -> Self {
+        Self {
+            min_threshold: 0,
+            required_oracles: Vec::new(env),
+        }
+    }
+}
+
+/// Name, version and salt of the EIP-712 domain the oracles of a consumer sign with.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct DomainConfig {
+    pub name: String,
+    pub version: String,
+    pub salt: BytesN<32>,
+}
+
+/// Whether the dry run could check a signature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[contracttype]
+pub enum SignatureStatus {
+    /// A signer was recovered from the secp256k1 signature.
+    Valid,
+    /// The secp256k1 signature is malformed.
+    Invalid,
+    /// Ed25519 signatures are not checked by the dry run, since invalid ones trap the host.
+    Unverified,
+}
+
+/// Outcome of the dry run of a single signature.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct SignatureDiagnostic {
+    /// Recovered secp256k1 address, or the ed25519 key at the referenced index. Zero if neither
+    /// could be determined.
+    pub signer: OracleKey,
+    pub status: SignatureStatus,
+    /// Whether the signer is an oracle of the active configuration.
+    pub member: bool,
+    /// Whether the signer comes after the previous signer of the same scheme.
+    pub ordered: bool,
+}
+
+/// Result of `diagnose_signatures`, with the values the oracles are expected to sign.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct SignatureDiagnostics {
+    pub epoch: u32,
+    pub domain_separator: BytesN<32>,
+    pub config_digest: BytesN<32>,
+    pub msg_digest: BytesN<32>,
+    pub signatures: Vec<SignatureDiagnostic>,
+    /// Total weight of the valid, ordered signatures of members, unverified signatures are not
+    /// counted.
+    pub weight: u32,
+    pub threshold: u32,
+}
+
+/// Reason signatures failed verification, naming the group whose minimum was not met.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VerificationError {
+    Error(Error),
+    GroupQuorumNotMet(Symbol),
+}
+
+impl From<VerificationError> for Error {
+    fn from(error: VerificationError) -> Self {
+        match error {
+            VerificationError::Error(error) => error,
+            VerificationError::GroupQuorumNotMet(_) => Error::GroupQuorumNotMet,
+        }
+    }
+}
+
+impl From<Error> for VerificationError {
+    fn from(error: Error) -> Self {
+        VerificationError::Error(error)
+    }
+}
+
+pub fn init(
+    env: &Env,
+    proxy: &Address,
+    threshold: u32,
+    oracles: Vec<EthAddress>,
+    domain: Option<DomainConfig>,
+) -> Result<(), Error> {
+    if has_proxy(env) {
         panic!("already initialized")
     }
-    // precalculate and store the domain separator
-    set_domain_separator(env, &domain_separator(env, &env.current_contract_address()));
     set_proxy(env, proxy);
-    set_threshold(env, threshold);
-    for oracle in oracles {
-        add_oracle(env, oracle);
+    init_oracles(env, threshold, oracles, domain)
+}
+
+/// Initialize without any oracles, accepting responses without signatures. Development only.
+pub fn init_insecure(env: &Env, proxy: &Address) -> Result<(), Error> {
+    set_insecure_mode(env, true)?;
+    init(env, proxy, 0, Vec::new(env), None)
+}
+
+/// Initialize the signature verification state only, for contracts that are not called by a proxy.
+/// Without a domain configuration the oracles sign with the default Relink domain.
+pub fn init_oracles(
+    env: &Env,
+    threshold: u32,
+    oracles: Vec<EthAddress>,
+    domain: Option<DomainConfig>,
+) -> Result<(), Error> {
+    if has_domain_separator(env) {
+        panic!("already initialized")
     }
+    // precalculate and store the domain separator
+    match domain {
+        Some(domain) => store_domain(env, &domain),
+        None => set_domain_separator(env, &domain_separator(env, &env.current_contract_address())),
+    }
+    store_config(
+        env,
+        OracleConfig {
+            oracles: equal_weights(env, oracles),
+            threshold,
+            group_minimums: Map::new(env),
+            activation_ledger: env.ledger().sequence(),
+        },
+    )?;
+    Ok(())
 }
 
 pub fn set_domain_separator(env: &Env, domain_separator: &BytesN<32>) {
@@ -35,9 +239,43 @@ pub fn get_domain_separator(env: &Env) -> BytesN<32> {
     env.storage().instance().get(&key).unwrap()
 }
 
+pub fn has_domain_separator(env: &Env) -> bool {
+    let key = DataKeyConsumer::DomainSeparator;
+    env.storage().instance().has(&key)
+}
+
+/// Switch to another signing domain, signatures for the previous domain are no longer accepted.
+pub fn set_domain(env: &Env, domain: &DomainConfig) {
+    store_domain(env, domain);
+    events::domain_set(env, &get_domain(env), &get_domain_separator(env));
+}
+
+/// Return the EIP-712 domain the oracles have to sign with.
+pub fn get_domain(env: &Env) -> Domain {
+    env.storage()
+        .instance()
+        .get(&DataKeyConsumer::Domain)
+        .unwrap_or_else(|| Domain::relink(env, &env.current_contract_address()))
+}
+
+/// Store the domain together with its precalculated separator.
+fn store_domain(env: &Env, config: &DomainConfig) {
+    let domain = Domain {
+        name: config.name.clone(),
+        version: config.version.clone(),
+        salt: config.salt.clone(),
+        verifying_contract: env.current_contract_address(),
+    };
+    set_domain_separator(env, &domain.separator(env));
+    env.storage()
+        .instance()
+        .set(&DataKeyConsumer::Domain, &domain);
+}
+
 pub fn set_proxy(env: &Env, proxy: &Address) {
     let key = DataKeyConsumer::Proxy;
     env.storage().instance().set(&key, proxy);
+    events::proxy_set(env, proxy);
 }
 
 pub fn get_proxy(env: &Env) -> Address {
@@ -50,38 +288,551 @@ pub fn has_proxy(env: &Env) -> bool {
     env.storage().instance().has(&key)
 }
 
-pub fn set_threshold(env: &Env, threshold: u32) {
+/// Return the most recently created configuration epoch, which might not be active yet.
+pub fn get_config_epoch(env: &Env) -> u32 {
     env.storage()
         .instance()
-        .set(&DataKeyConsumer::Threshold, &threshold);
-    events::threshold_set(env, threshold);
+        .get(&DataKeyConsumer::ConfigEpoch)
+        .unwrap_or(0)
 }
 
-pub fn get_threshold(env: &Env) -> u32 {
+pub fn get_config(env: &Env, epoch: u32) -> Option<OracleConfig> {
     env.storage()
         .instance()
-        .get(&DataKeyConsumer::Threshold)
-        .unwrap()
+        .get(&DataKeyConsumer::Config(epoch))
+}
+
+/// Return the epoch of the configuration currently used for verification.
+pub fn get_active_epoch(env: &Env) -> u32 {
+    let sequence = env.ledger().sequence();
+    let mut epoch = get_config_epoch(env);
+    // skip configurations scheduled for the future or replaced before their activation
+    while epoch > 1
+        && !get_config(env, epoch).is_some_and(|config| config.activation_ledger <= sequence)
+    {
+        epoch -= 1;
+    }
+    epoch
 }
 
-pub fn add_oracle(env: &Env, oracle: EthAddress) {
+/// Return the configuration currently used for verification.
+pub fn get_active_config(env: &Env) -> OracleConfig {
+    get_config(env, get_active_epoch(env)).unwrap()
+}
+
+/// Return the config digest the oracles of the active configuration have to sign.
+pub fn get_config_digest(env: &Env) -> BytesN<32> {
+    let epoch = get_active_epoch(env);
+    config_digest(env, epoch, &get_config(env, epoch).unwrap())
+}
+
+/// Schedule a new configuration with equally weighted oracles, the previous one stays valid
+/// during the overlap window after the activation ledger.
+pub fn set_oracle_config(
+    env: &Env,
+    oracles: Vec<EthAddress>,
+    threshold: u32,
+    activation_ledger: u32,
+) -> Result<u32, Error> {
+    set_weighted_oracle_config(
+        env,
+        equal_weights(env, oracles),
+        threshold,
+        Map::new(env),
+        activation_ledger,
+    )
+}
+
+/// Schedule a new configuration with weighted oracles, the threshold is the sum of the weights
+/// required. Oracles can be assigned to groups, with the minimum number of signers per group
+/// given in `group_minimums`.
+pub fn set_weighted_oracle_config(
+    env: &Env,
+    oracles: Vec<OracleEntry>,
+    threshold: u32,
+    group_minimums: Map<Symbol, u32>,
+    activation_ledger: u32,
+) -> Result<u32, Error> {
+    // configurations are activated in the order they are created, never in the past
+    let latest = get_config(env, get_config_epoch(env)).unwrap();
+    if activation_ledger < env.ledger().sequence() || activation_ledger < latest.activation_ledger {
+        return Err(Error::InvalidActivationLedger);
+    }
+    store_config(
+        env,
+        OracleConfig {
+            oracles,
+            threshold,
+            group_minimums,
+            activation_ledger,
+        },
+    )
+}
+
+/// Replace the oracles without the owner, authorized by signatures of the oracles of the active
+/// configuration over a `ConfigUpdate` message. The nonce has to match `get_config_update_nonce`,
+/// which is incremented with every update. The new configuration is active immediately and the
+/// current one is no longer accepted, without an overlap window. As the message only carries
+/// addresses, the update is rejected with `UnsupportedConfig` if the active configuration has
+/// weights, groups or ed25519 oracles, or if a configuration is scheduled.
+pub fn update_oracle_config(
+    env: &Env,
+    oracles: Vec<EthAddress>,
+    threshold: u32,
+    nonce: u64,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<u32, Error> {
+    if nonce != get_config_update_nonce(env) {
+        return Err(Error::InvalidNonce);
+    }
+    // only the current oracles can govern, not the previous ones during an overlap window
+    let epoch = get_active_epoch(env);
+    let config = get_config(env, epoch).unwrap();
+    // without a threshold anybody could take over the consumer
+    if config.threshold == 0 {
+        return Err(Error::UnauthorizedOracleSignatures);
+    }
+    // the update must not drop settings it can't express, nor replace a scheduled configuration
+    if !is_plain_config(&config) || get_config_epoch(env) != epoch {
+        return Err(Error::UnsupportedConfig);
+    }
+    verify_config_signatures(
+        env,
+        epoch,
+        &config,
+        &|config_digest| config_update_hash(env, &oracles, threshold, nonce, config_digest),
+        signatures,
+        &VerificationPolicy::new(env),
+    )
+    .map_err(|error| log_error(env, error))?;
     env.storage()
         .instance()
-        .set(&DataKeyConsumer::Oracle(oracle.clone()), &());
-    events::oracle_added(env, oracle);
+        .set(&DataKeyConsumer::ConfigUpdateNonce, &(nonce + 1));
+    let epoch = set_oracle_config(env, oracles, threshold, env.ledger().sequence())?;
+    // the update may remove a compromised oracle, the replaced configuration ends right away
+    end_previous_config(env, epoch);
+    Ok(epoch)
 }
 
-pub fn remove_oracle(env: &Env, oracle: EthAddress) {
+/// Whether the configuration only has secp256k1 oracles of weight 1 without groups, which is all
+/// a `ConfigUpdate` message can express.
+fn is_plain_config(config: &OracleConfig) -> bool {
+    config.group_minimums.is_empty()
+        && config.oracles.iter().all(|oracle| {
+            matches!(oracle.key, OracleKey::Secp256k1(_))
+                && oracle.weight == 1
+                && oracle.groups.is_empty()
+        })
+}
+
+/// Return the nonce the next `ConfigUpdate` message has to be signed with.
+pub fn get_config_update_nonce(env: &Env) -> u64 {
     env.storage()
         .instance()
-        .remove(&DataKeyConsumer::Oracle(oracle.clone()));
-    events::oracle_removed(env, oracle);
+        .get(&DataKeyConsumer::ConfigUpdateNonce)
+        .unwrap_or(0)
 }
 
-pub fn has_oracle(env: &Env, oracle: EthAddress) -> bool {
+/// Store the configuration as a new epoch.
+fn store_config(env: &Env, config: OracleConfig) -> Result<u32, Error> {
+    validate_config(env, &config)?;
+    let epoch = get_config_epoch(env) + 1;
     env.storage()
         .instance()
-        .has(&DataKeyConsumer::Oracle(oracle))
+        .set(&DataKeyConsumer::Config(epoch), &config);
+    env.storage()
+        .instance()
+        .set(&DataKeyConsumer::ConfigEpoch, &epoch);
+    events::oracle_config_set(env, epoch, &config, &config_digest(env, epoch, &config));
+    // prune configurations which can no longer be used for verification
+    let mut pruned = get_active_epoch(env).saturating_sub(2);
+    while pruned > 0
+        && env
+            .storage()
+            .instance()
+            .has(&DataKeyConsumer::Config(pruned))
+    {
+        env.storage()
+            .instance()
+            .remove(&DataKeyConsumer::Config(pruned));
+        pruned -= 1;
+    }
+    Ok(epoch)
+}
+
+/// Check that the oracles are unique and the threshold is reachable. A threshold of zero is only
+/// accepted in insecure mode.
+fn validate_config(env: &Env, config: &OracleConfig) -> Result<(), Error> {
+    let mut total_weight: u32 = 0;
+    for (index, oracle) in config.oracles.iter().enumerate() {
+        if oracle.weight == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        if find_oracle(&config.oracles.slice(..index as u32), &oracle.key).is_some() {
+            return Err(Error::DuplicateOracle);
+        }
+        // a label counted twice would let a single oracle meet a group minimum of two
+        for (position, group) in oracle.groups.iter().enumerate() {
+            if oracle.groups.slice(..position as u32).contains(&group) {
+                return Err(Error::DuplicateGroup);
+            }
+        }
+        total_weight = total_weight
+            .checked_add(oracle.weight)
+            .ok_or(Error::InvalidWeight)?;
+    }
+    if config.threshold > total_weight || (config.threshold == 0 && !is_insecure_mode(env)) {
+        return Err(Error::InvalidThreshold);
+    }
+    // every group minimum must be reachable by the members of the group
+    for (group, minimum) in config.group_minimums.iter() {
+        let members = config
+            .oracles
+            .iter()
+            .filter(|oracle| oracle.groups.contains(&group))
+            .count() as u32;
+        if minimum == 0 || minimum > members {
+            return Err(Error::InvalidThreshold);
+        }
+    }
+    Ok(())
+}
+
+fn equal_weights(env: &Env, oracles: Vec<EthAddress>) -> Vec<OracleEntry> {
+    let mut entries = Vec::new(env);
+    for oracle in oracles.iter() {
+        entries.push_back(OracleEntry::new(env, oracle));
+    }
+    entries
+}
+
+/// Return the index of the oracle in the entries.
+fn find_oracle(oracles: &Vec<OracleEntry>, key: &OracleKey) -> Option<u32> {
+    oracles
+        .iter()
+        .position(|oracle| oracle.key == *key)
+        .map(|index| index as u32)
+}
+
+/// Check that the active configuration can satisfy the policy: its oracles have to carry the
+/// minimum threshold, and the required oracles have to be among them.
+pub fn validate_policy(env: &Env, policy: &VerificationPolicy) -> Result<(), Error> {
+    let config = get_active_config(env);
+    let total_weight = config
+        .oracles
+        .iter()
+        .fold(0u32, |total, oracle| total.saturating_add(oracle.weight));
+    if policy.min_threshold > total_weight {
+        return Err(Error::InvalidThreshold);
+    }
+    for key in policy.required_oracles.iter() {
+        if find_oracle(&config.oracles, &key).is_none() {
+            return Err(Error::OracleUnknown);
+        }
+    }
+    Ok(())
+}
+
+/// Derive a new configuration from the most recent one, active immediately or together with a
+/// scheduled one. The configuration it replaces is no longer accepted, so that removing an oracle,
+/// raising the threshold or lowering a weight takes effect in the next ledger. Only rotations
+/// scheduled with `set_oracle_config` keep the previous configuration during the overlap window.
+fn update_config(
+    env: &Env,
+    f: impl FnOnce(&mut OracleConfig) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut config = get_config(env, get_config_epoch(env)).unwrap();
+    f(&mut config)?;
+    config.activation_ledger = config.activation_ledger.max(env.ledger().sequence());
+    let epoch = store_config(env, config)?;
+    end_previous_config(env, epoch);
+    Ok(())
+}
+
+/// Drop the configuration preceding `epoch`, ending its overlap window right away.
+fn end_previous_config(env: &Env, epoch: u32) {
+    env.storage()
+        .instance()
+        .remove(&DataKeyConsumer::Config(epoch - 1));
+}
+
+/// Allow a threshold of zero, which disables the signature verification. Development only.
+/// Leaving insecure mode drops a previous configuration without threshold, rather than accepting
+/// it until its overlap window ends.
+pub fn set_insecure_mode(env: &Env, enabled: bool) -> Result<(), Error> {
+    // insecure mode cannot be left while a configuration without signatures is in use
+    if !enabled && get_config_epoch(env) > 0 {
+        let active = get_active_epoch(env);
+        for epoch in active..=get_config_epoch(env) {
+            if get_config(env, epoch).is_some_and(|config| config.threshold == 0) {
+                return Err(Error::InvalidThreshold);
+            }
+        }
+        // end the overlap window of a previous configuration without signatures
+        if get_config(env, active - 1).is_some_and(|previous| previous.threshold == 0) {
+            end_previous_config(env, active);
+        }
+    }
+    env.storage()
+        .instance()
+        .set(&DataKeyConsumer::InsecureMode, &enabled);
+    events::insecure_mode_set(env, enabled);
+    Ok(())
+}
+
+pub fn is_insecure_mode(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyConsumer::InsecureMode)
+        .unwrap_or(false)
+}
+
+pub fn set_overlap_window(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKeyConsumer::OverlapWindow, &ledgers);
+    events::overlap_window_set(env, ledgers);
+}
+
+/// Return the number of ledgers after the activation of a configuration during which the previous
+/// configuration is still accepted.
+pub fn get_overlap_window(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyConsumer::OverlapWindow)
+        .unwrap_or(DEFAULT_OVERLAP_WINDOW)
+}
+
+/// Set the number of ledgers after a request during which its response is accepted, zero
+/// disables the deadline. The delay can't exceed `REQUEST_TTL`, the ledger a request was made at
+/// is not kept any longer.
+pub fn set_max_fulfillment_delay(env: &Env, ledgers: u32) -> Result<(), Error> {
+    if ledgers > REQUEST_TTL {
+        return Err(Error::InvalidFulfillmentDelay);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKeyConsumer::MaxFulfillmentDelay, &ledgers);
+    events::max_fulfillment_delay_set(env, ledgers);
+    Ok(())
+}
+
+/// Return the number of ledgers after a request during which its response is accepted, zero if
+/// there is no deadline.
+pub fn get_max_fulfillment_delay(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyConsumer::MaxFulfillmentDelay)
+        .unwrap_or(0)
+}
+
+/// Return the ledger sequence at which the request was made, if it was made by this consumer
+/// and is not older than `REQUEST_TTL`.
+pub fn get_request_ledger(env: &Env, id: &RequestId) -> Option<u32> {
+    let key = DataKeyConsumer::RequestLedger(id.clone());
+    env.storage().temporary().get(&key)
+}
+
+/// Return the last ledger sequence at which a response to the request is accepted, if there is
+/// a deadline.
+pub fn get_response_deadline(env: &Env, id: &RequestId) -> Option<u32> {
+    let delay = get_max_fulfillment_delay(env);
+    if delay == 0 {
+        return None;
+    }
+    get_request_ledger(env, id).map(|ledger| ledger.saturating_add(delay))
+}
+
+fn set_request_ledger(env: &Env, id: &RequestId) {
+    let key = DataKeyConsumer::RequestLedger(id.clone());
+    env.storage()
+        .temporary()
+        .set(&key, &env.ledger().sequence());
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, REQUEST_TTL, REQUEST_TTL);
+}
+
+fn remove_request_ledger(env: &Env, id: &RequestId) {
+    let key = DataKeyConsumer::RequestLedger(id.clone());
+    env.storage().temporary().remove(&key);
+}
+
+/// Reject responses arriving after the deadline of the request. Without the ledger the request
+/// was made at the deadline is unknown, so the response is rejected as well.
+fn check_deadline(env: &Env, id: &RequestId) -> Result<(), Error> {
+    if get_max_fulfillment_delay(env) == 0 {
+        return Ok(());
+    }
+    match get_response_deadline(env, id) {
+        Some(deadline) if env.ledger().sequence() <= deadline => Ok(()),
+        _ => Err(Error::ResponseExpired),
+    }
+}
+
+pub fn set_threshold(env: &Env, threshold: u32) -> Result<(), Error> {
+    update_config(env, |config| {
+        config.threshold = threshold;
+        Ok(())
+    })?;
+    events::threshold_set(env, threshold);
+    Ok(())
+}
+
+pub fn get_threshold(env: &Env) -> u32 {
+    get_active_config(env).threshold
+}
+
+pub fn add_oracle(env: &Env, oracle: EthAddress) -> Result<(), Error> {
+    add_oracle_key(env, oracle.into())
+}
+
+/// Add an oracle of any signature scheme.
+pub fn add_oracle_key(env: &Env, oracle: OracleKey) -> Result<(), Error> {
+    update_config(env, |config| {
+        config
+            .oracles
+            .push_back(OracleEntry::new(env, oracle.clone()));
+        Ok(())
+    })?;
+    match oracle {
+        OracleKey::Secp256k1(address) => events::oracle_added(env, address),
+        OracleKey::Ed25519(public_key) => events::ed25519_oracle_added(env, public_key),
+    }
+    Ok(())
+}
+
+/// Update the weight of an oracle's signature.
+pub fn set_oracle_weight(env: &Env, oracle: OracleKey, weight: u32) -> Result<(), Error> {
+    update_config(env, |config| {
+        let index = find_oracle(&config.oracles, &oracle).ok_or(Error::OracleUnknown)?;
+        let mut entry = config.oracles.get_unchecked(index);
+        entry.weight = weight;
+        config.oracles.set(index, entry);
+        Ok(())
+    })?;
+    events::oracle_weight_set(env, oracle, weight);
+    Ok(())
+}
+
+/// Replace the labels of the groups an oracle belongs to.
+pub fn set_oracle_groups(env: &Env, oracle: OracleKey, groups: Vec<Symbol>) -> Result<(), Error> {
+    update_config(env, |config| {
+        let index = find_oracle(&config.oracles, &oracle).ok_or(Error::OracleUnknown)?;
+        let mut entry = config.oracles.get_unchecked(index);
+        entry.groups = groups.clone();
+        config.oracles.set(index, entry);
+        Ok(())
+    })?;
+    events::oracle_groups_set(env, oracle, groups);
+    Ok(())
+}
+
+/// Require at least `minimum` oracles of the group to sign, a minimum of zero removes the rule.
+pub fn set_group_minimum(env: &Env, group: Symbol, minimum: u32) -> Result<(), Error> {
+    update_config(env, |config| {
+        if minimum == 0 {
+            config.group_minimums.remove(group.clone());
+        } else {
+            config.group_minimums.set(group.clone(), minimum);
+        }
+        Ok(())
+    })?;
+    events::group_minimum_set(env, group, minimum);
+    Ok(())
+}
+
+/// Return the minimum number of signers per group of the active configuration.
+pub fn get_group_minimums(env: &Env) -> Map<Symbol, u32> {
+    get_active_config(env).group_minimums
+}
+
+/// Return the weight of the oracle in the active configuration, zero if it is not permitted.
+pub fn get_oracle_weight(env: &Env, oracle: OracleKey) -> u32 {
+    let oracles = get_active_config(env).oracles;
+    find_oracle(&oracles, &oracle)
+        .map(|index| oracles.get_unchecked(index).weight)
+        .unwrap_or(0)
+}
+
+/// Remove an oracle, fails if the threshold would no longer be reachable.
+pub fn remove_oracle(env: &Env, oracle: EthAddress) -> Result<(), Error> {
+    remove_oracle_key(env, oracle.into())
+}
+
+/// Remove an oracle of any signature scheme.
+pub fn remove_oracle_key(env: &Env, oracle: OracleKey) -> Result<(), Error> {
+    update_config(env, |config| {
+        let index = find_oracle(&config.oracles, &oracle).ok_or(Error::OracleUnknown)?;
+        config.oracles.remove(index);
+        Ok(())
+    })?;
+    match oracle {
+        OracleKey::Secp256k1(address) => events::oracle_removed(env, address),
+        OracleKey::Ed25519(public_key) => events::ed25519_oracle_removed(env, public_key),
+    }
+    Ok(())
+}
+
+pub fn has_oracle(env: &Env, oracle: EthAddress) -> bool {
+    has_oracle_key(env, oracle.into())
+}
+
+pub fn has_oracle_key(env: &Env, oracle: OracleKey) -> bool {
+    find_oracle(&get_active_config(env).oracles, &oracle).is_some()
+}
+
+/// Return the number of oracles in the active configuration.
+pub fn get_oracle_count(env: &Env) -> u32 {
+    get_active_config(env).oracles.len()
+}
+
+/// Implement Solidity equivalent config digest:
+/// bytes32 configDigest = keccak256(
+///     abi.encode(
+///         ORACLE_CONFIG_HASH,
+///         epoch,
+///         keccak256(abi.encodePacked(oracles)),
+///         keccak256(abi.encodePacked(weights)),
+///         keccak256(abi.encodePacked(groups)),
+///         groupMinimums,
+///         threshold
+///     )
+/// );
+/// Array elements are left-padded to 32 bytes each, as in EIP-712. Secp256k1 oracles are encoded
+/// by their address, ed25519 oracles by their public key. The group labels of an oracle and the
+/// group minimums have no Solidity equivalent, they are encoded as the keccak256 hash of their XDR
+/// serialization.
+pub fn config_digest(env: &Env, epoch: u32, config: &OracleConfig) -> BytesN<32> {
+    // Keccak256 of "OracleConfig(uint32 epoch,bytes32[] oracles,uint32[] weights,bytes32[] groups,bytes32 groupMinimums,uint32 threshold)"
+    let prefix_hash = bytesn!(
+        env,
+        0x9f7df459682c5794c39be23d826e2cd08995255b370be05ec7c4f10342b96e03
+    );
+    let crypto = env.crypto();
+    let mut oracles = Vec::new(env);
+    let mut weights = Vec::new(env);
+    let mut groups = Vec::new(env);
+    for oracle in config.oracles.iter() {
+        oracles.push_back(match oracle.key {
+            OracleKey::Secp256k1(address) => {
+                let mut word = [0u8; 32];
+                word[12..].copy_from_slice(&address.as_bytes().to_array());
+                BytesN::from_array(env, &word)
+            }
+            OracleKey::Ed25519(public_key) => public_key,
+        });
+        weights.push_back(oracle.weight);
+        groups.push_back(crypto.keccak256(&oracle.groups.to_xdr(env)));
+    }
+    Encoder::new(env, &prefix_hash)
+        .uint32(epoch)
+        .bytes32_array(&oracles)
+        .uint32_array(&weights)
+        .bytes32_array(&groups)
+        .bytes32(&crypto.keccak256(&config.group_minimums.clone().to_xdr(env)))
+        .uint32(config.threshold)
+        .hash()
 }
 
 /// Implement Solidity equivalent domain separator:
@@ -96,33 +847,7 @@ pub fn has_oracle(env: &Env, oracle: EthAddress) -> bool {
 ///     )
 /// );
 pub fn domain_separator(env: &Env, contract: &Address) -> BytesN<32> {
-    // Keccak256 of "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract,bytes32 salt)"
-    let prefix_hash = bytes!(
-        env,
-        0xd87cd6ef79d4e2b95e15ce8abf732db51ec771f1ca2edccf22a46c729ac56472
-    );
-    // Keccak256 of "Relink MultiSig"
-    let name_hash = bytes!(
-        env,
-        0x615770db9463494f4a7a0575fdd7fbbbdd2ac99f24e8a7960d5bd346cfa1dbf7
-    );
-    // Keccak256 of "1"
-    let version_hash = bytes!(
-        env,
-        0xc89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6
-    );
-    let salt = bytes!(
-        env,
-        0x151543af6b722378665a73fe38dbceae4871a070b7cdaf5c6e30cf758dc33cc8
-    );
-    let mut domain = Bytes::new(env);
-    domain.append(&prefix_hash);
-    domain.append(&name_hash);
-    domain.append(&version_hash);
-    domain.append(env.ledger().network_id().as_ref());
-    domain.append(&address_bytes(env, &contract));
-    domain.append(&salt);
-    env.crypto().keccak256(&domain)
+    Domain::relink(env, contract).separator(env)
 }
 
 /// Implement Solidity equivalent tx input hash:
@@ -130,28 +855,56 @@ pub fn domain_separator(env: &Env, contract: &Address) -> BytesN<32> {
 ///     abi.encode(
 ///         RANDOMNESS_RECEIVED_HASH,
 ///         requestId,
-///         keccak256(abi.encodePacked(randomWords))
+///         keccak256(abi.encodePacked(randomWords)),
+///         configDigest
 ///     )
 /// );
-pub fn tx_input_hash(env: &Env, id: &RequestId, random_words: &Vec<BytesN<32>>) -> BytesN<32> {
-    // Keccak256 of "ProxyRequest(bytes32 requestId,uint256[] randomWords)"
-    let prefix_hash = bytes!(
+pub fn tx_input_hash(
+    env: &Env,
+    id: &RequestId,
+    random_words: &Vec<BytesN<32>>,
+    config_digest: &BytesN<32>,
+) -> BytesN<32> {
+    // Keccak256 of "ProxyRequest(bytes32 requestId,uint256[] randomWords,bytes32 configDigest)"
+    let prefix_hash = bytesn!(
         env,
-        0x910684821c06c34275feee35f4ae2d80136d3b2e7366d0795b2eb4116d82ac07
+        0xec4f03358c7f1daf351b4bee709e94cc2460ed6a6a65e1311cdb6f21ea037577
     );
-    let crypto = env.crypto();
-    // hash the random words
-    let mut buffer = Bytes::new(env);
-    for word in random_words.iter() {
-        buffer.append(word.as_ref());
-    }
-    let random_words_hash = crypto.keccak256(&buffer);
-    // hash the tx inputs
-    let mut buffer = Bytes::new(env);
-    buffer.append(&prefix_hash);
-    buffer.append(id.as_bytes().as_ref());
-    buffer.append(random_words_hash.as_ref());
-    crypto.keccak256(&buffer)
+    Encoder::new(env, &prefix_hash)
+        .bytes32(id.as_bytes())
+        .bytes32_array(random_words)
+        .bytes32(config_digest)
+        .hash()
+}
+
+/// Implement Solidity equivalent config update hash:
+/// bytes32 configUpdateHash = keccak256(
+///     abi.encode(
+///         CONFIG_UPDATE_HASH,
+///         keccak256(abi.encode(oracles)),
+///         threshold,
+///         nonce,
+///         configDigest
+///     )
+/// );
+pub fn config_update_hash(
+    env: &Env,
+    oracles: &Vec<EthAddress>,
+    threshold: u32,
+    nonce: u64,
+    config_digest: &BytesN<32>,
+) -> BytesN<32> {
+    // Keccak256 of "ConfigUpdate(address[] oracles,uint32 threshold,uint64 nonce,bytes32 configDigest)"
+    let prefix_hash = bytesn!(
+        env,
+        0xf1c09713efc794c451ebc79441443e3b6362c4b757d8bd7cad940d3eec0b6e8f
+    );
+    Encoder::new(env, &prefix_hash)
+        .address_array(oracles)
+        .uint32(threshold)
+        .uint64(nonce)
+        .bytes32(config_digest)
+        .hash()
 }
 
 pub fn request_randomness(
@@ -164,14 +917,59 @@ pub fn request_randomness(
     // require authorization by origin
     origin.require_auth();
     // call to the proxy contract
-    let proxy_client = VrfDirectFundingProxyClient::new(env, &get_proxy(&env));
-    proxy_client.request_randomness(
+    let proxy_client = VrfDirectFundingProxyClient::new(env, &get_proxy(env));
+    let id = proxy_client.request_randomness(
         &origin,
         &value,
         &env.current_contract_address(),
         &request_confirmations.unwrap_or(3),
         &num_words.unwrap_or(1),
-    )
+    );
+    // remember when the request was made to enforce the response deadline
+    set_request_ledger(env, &id);
+    id
+}
+
+/// Request randomness paid by the consumer contract itself, for consumers which keep their own
+/// balance. `token` has to be the fee token of the proxy, errors of the proxy are returned. If the
+/// proxy call fails otherwise, for example because the fee transfer is not the one authorized,
+/// `RandomnessRequestFailed` is returned.
+pub fn request_randomness_from_contract(
+    env: &Env,
+    token: &Address,
+    value: i128,
+    request_confirmations: Option<u32>,
+    num_words: Option<u32>,
+) -> Result<RequestId, Error> {
+    let this_contract = env.current_contract_address();
+    let proxy = get_proxy(env);
+    // the proxy transfers the fee from this contract, which has to be authorized upfront
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (this_contract.clone(), proxy.clone(), value).into_val(env),
+            },
+            sub_invocations: Vec::new(env),
+        }),
+    ]);
+    let proxy_client = VrfDirectFundingProxyClient::new(env, &proxy);
+    let id = match proxy_client.try_request_randomness(
+        &this_contract,
+        &value,
+        &this_contract,
+        &request_confirmations.unwrap_or(3),
+        &num_words.unwrap_or(1),
+    ) {
+        Ok(Ok(id)) => id,
+        Err(Ok(err)) => return Err(err),
+        _ => return Err(Error::RandomnessRequestFailed),
+    };
+    // remember when the request was made to enforce the response deadline
+    set_request_ledger(env, &id);
+    Ok(id)
 }
 
 pub fn verify_randomness(
@@ -179,44 +977,528 @@ pub fn verify_randomness(
     id: &RequestId,
     random_words: &Vec<BytesN<32>>,
     signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    verify_randomness_with_policy(
+        env,
+        id,
+        random_words,
+        signatures,
+        &VerificationPolicy::new(env),
+    )
+}
+
+/// Same as `verify_randomness`, but the signatures also have to satisfy the policy of the request.
+pub fn verify_randomness_with_policy(
+    env: &Env,
+    id: &RequestId,
+    random_words: &Vec<BytesN<32>>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
 ) -> Result<(), Error> {
     // must be called by the proxy
     get_proxy(env).require_auth();
+    // reject late responses
+    check_deadline(env, id)?;
     // verify oracle signatures
-    let threshold = get_threshold(env);
-    if signatures.len() < threshold {
-        return Err(Error::TooFewSignatures);
-    }
-    // reproduce message-to-sign
-    // bytes32 totalHash = keccak256(
-    //     abi.encodePacked("\x19\x01", domainSeparator, txInputHash)
-    // );
-    let mut msg = Bytes::from_array(env, &[0x19, 0x01]);
-    msg.append(get_domain_separator(env).as_ref());
-    msg.append(tx_input_hash(env, id, random_words).as_ref());
+    verify_signatures_with_policy(
+        env,
+        |config_digest| tx_input_hash(env, id, random_words, config_digest),
+        signatures,
+        policy,
+    )?;
+    remove_request_ledger(env, id);
+    Ok(())
+}
+
+/// Check that the request id was derived from the origin and nonce for this consumer and proxy.
+pub fn verify_request_id(
+    env: &Env,
+    id: &RequestId,
+    origin: &Address,
+    nonce: u128,
+) -> Result<(), Error> {
+    let expected = RequestId::new(
+        env,
+        origin,
+        &env.current_contract_address(),
+        &get_proxy(env),
+        nonce,
+    );
+    if *id != expected {
+        return Err(Error::RequestIdMismatch);
+    }
+    Ok(())
+}
+
+/// Same as `verify_randomness`, but also check that the request was made for this consumer,
+/// which does not require the consumer to keep track of its pending requests.
+pub fn verify_randomness_v2(
+    env: &Env,
+    id: &RequestId,
+    origin: &Address,
+    nonce: u128,
+    random_words: &Vec<BytesN<32>>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    verify_request_id(env, id, origin, nonce)?;
+    verify_randomness(env, id, random_words, signatures)
+}
+
+/// Same as `verify_randomness`, but with the signers given as a bitmap of oracle indexes.
+pub fn verify_randomness_indexed(
+    env: &Env,
+    id: &RequestId,
+    random_words: &Vec<BytesN<32>>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    verify_randomness_indexed_with_policy(
+        env,
+        id,
+        random_words,
+        signer_bitmap,
+        signatures,
+        &VerificationPolicy::new(env),
+    )
+}
+
+/// Same as `verify_randomness_indexed`, but the signatures also have to satisfy the policy of the
+/// request.
+pub fn verify_randomness_indexed_with_policy(
+    env: &Env,
+    id: &RequestId,
+    random_words: &Vec<BytesN<32>>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), Error> {
+    // must be called by the proxy
+    get_proxy(env).require_auth();
+    // reject late responses
+    check_deadline(env, id)?;
+    // verify oracle signatures
+    verify_signatures_indexed_with_policy(
+        env,
+        |config_digest| tx_input_hash(env, id, random_words, config_digest),
+        signer_bitmap,
+        signatures,
+        policy,
+    )?;
+    remove_request_ledger(env, id);
+    Ok(())
+}
+
+/// Verify that the weights of the permitted oracles which signed the typed data hash produced by
+/// `tx_input_hash` for the digest of the active configuration add up to at least `threshold`,
+/// and that the minimum of every group is met. During the overlap window the previous
+/// configuration is accepted as well.
+pub fn verify_signatures(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    check_signatures(env, tx_input_hash, signatures).map_err(|error| log_error(env, error))
+}
+
+/// Same as `verify_signatures`, but the total weight of the signers also has to reach the minimum
+/// threshold of the policy, and the oracles it requires have to be among the signers.
+pub fn verify_signatures_with_policy(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), Error> {
+    check_signatures_with_policy(env, tx_input_hash, signatures, policy)
+        .map_err(|error| log_error(env, error))
+}
+
+/// Same as `verify_signatures`, but the error names the group whose minimum was not met.
+pub fn check_signatures(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), VerificationError> {
+    check_signatures_with_policy(
+        env,
+        tx_input_hash,
+        signatures,
+        &VerificationPolicy::new(env),
+    )
+}
+
+/// Same as `verify_signatures_with_policy`, but the error names the group whose minimum was not
+/// met.
+pub fn check_signatures_with_policy(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), VerificationError> {
+    verify_with_overlap(env, signatures, |epoch, config| {
+        verify_config_signatures(env, epoch, config, &tx_input_hash, signatures, policy)
+    })
+}
+
+/// Verify signatures given in the indexed format: bit `i` of `signer_bitmap` is set if the oracle
+/// at index `i` of the configuration signed, and `signatures` holds one signature per set bit in
+/// ascending index order. Every signature is checked against the expected oracle only, so the
+/// cost depends on the number of signatures alone. The second element of a signature tuple is
+/// the recovery id of secp256k1 signatures, for ed25519 signatures it carries the
+/// `ED25519_SIGNATURE` flag and optionally `ED25519_PREVIOUS_EPOCH`, the index bits are ignored.
+pub fn verify_signatures_indexed(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    check_signatures_indexed(env, tx_input_hash, signer_bitmap, signatures)
+        .map_err(|error| log_error(env, error))
+}
+
+/// Same as `verify_signatures_indexed`, but the signers also have to satisfy the policy, see
+/// `verify_signatures_with_policy`.
+pub fn verify_signatures_indexed_with_policy(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), Error> {
+    check_signatures_indexed_with_policy(env, tx_input_hash, signer_bitmap, signatures, policy)
+        .map_err(|error| log_error(env, error))
+}
+
+/// Same as `verify_signatures_indexed`, but the error names the group whose minimum was not met.
+pub fn check_signatures_indexed(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), VerificationError> {
+    check_signatures_indexed_with_policy(
+        env,
+        tx_input_hash,
+        signer_bitmap,
+        signatures,
+        &VerificationPolicy::new(env),
+    )
+}
+
+/// Same as `verify_signatures_indexed_with_policy`, but the error names the group whose minimum
+/// was not met.
+pub fn check_signatures_indexed_with_policy(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), VerificationError> {
+    verify_with_overlap(env, signatures, |epoch, config| {
+        verify_config_signatures_indexed(
+            env,
+            epoch,
+            config,
+            &tx_input_hash,
+            signer_bitmap,
+            signatures,
+            policy,
+        )
+    })
+}
+
+fn log_error(env: &Env, error: VerificationError) -> Error {
+    if let VerificationError::GroupQuorumNotMet(group) = &error {
+        log!(env, "group quorum not met", group.clone());
+    }
+    error.into()
+}
+
+/// Verify against the active configuration, or during the overlap window the previous one. With
+/// ed25519 signatures only the configuration selected by their `ED25519_PREVIOUS_EPOCH` flag is
+/// tried.
+fn verify_with_overlap(
+    env: &Env,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    verify: impl Fn(u32, &OracleConfig) -> Result<(), VerificationError>,
+) -> Result<(), VerificationError> {
+    let epoch = get_active_epoch(env);
+    let config = get_config(env, epoch).unwrap();
+    let previous = || {
+        let overlap_end = config
+            .activation_ledger
+            .saturating_add(get_overlap_window(env));
+        if epoch > 1 && env.ledger().sequence() < overlap_end {
+            get_config(env, epoch - 1)
+        } else {
+            None
+        }
+    };
+    match ed25519_previous_epoch(signatures)? {
+        Some(false) => verify(epoch, &config),
+        Some(true) => match previous() {
+            Some(previous) => verify(epoch - 1, &previous),
+            None => Err(Error::UnauthorizedOracleSignatures.into()),
+        },
+        None => {
+            let result = verify(epoch, &config);
+            if result.is_err() {
+                if let Some(previous) = previous() {
+                    if verify(epoch - 1, &previous).is_ok() {
+                        return Ok(());
+                    }
+                }
+            }
+            result
+        }
+    }
+}
+
+/// Return whether the ed25519 signatures were made for the previous configuration, `None` if
+/// there are none. All of them have to agree.
+fn ed25519_previous_epoch(signatures: &Vec<(BytesN<64>, u32)>) -> Result<Option<bool>, Error> {
+    let mut previous = None;
+    for (_, flags) in signatures.iter() {
+        if flags & ED25519_SIGNATURE == 0 {
+            continue;
+        }
+        let flagged = flags & ED25519_PREVIOUS_EPOCH != 0;
+        if previous.is_some_and(|previous| previous != flagged) {
+            return Err(Error::InvalidSignature);
+        }
+        previous = Some(flagged);
+    }
+    Ok(previous)
+}
+
+/// Reproduce the message-to-sign.
+fn message_digest(
+    env: &Env,
+    epoch: u32,
+    config: &OracleConfig,
+    tx_input_hash: &impl Fn(&BytesN<32>) -> BytesN<32>,
+) -> BytesN<32> {
+    typed_data_digest(
+        env,
+        &get_domain_separator(env),
+        &tx_input_hash(&config_digest(env, epoch, config)),
+    )
+}
+
+fn verify_config_signatures(
+    env: &Env,
+    epoch: u32,
+    config: &OracleConfig,
+    tx_input_hash: &impl Fn(&BytesN<32>) -> BytesN<32>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), VerificationError> {
+    let max_weight = config
+        .oracles
+        .iter()
+        .map(|oracle| oracle.weight)
+        .max()
+        .unwrap_or(0);
+    if signatures.len().saturating_mul(max_weight) < config.threshold.max(policy.min_threshold) {
+        return Err(Error::TooFewSignatures.into());
+    }
     let crypto = env.crypto();
-    let msg_digest = crypto.keccak256(&msg);
-    // validate oracle signatures, recovering the addresses in the process, this will panic on invalid signatures
-    let mut valid: u32 = 0;
+    let msg_digest = message_digest(env, epoch, config, tx_input_hash);
+    // validate oracle signatures, recovering the addresses in the process, this will panic on
+    // invalid ed25519 signatures, which is why the epoch is chosen before by their flag
+    let mut signers: Vec<u32> = Vec::new(env);
     let mut last = EthAddress::zero(env);
+    let mut last_index: Option<u32> = None;
     for (signature, recovery_id) in signatures.iter() {
-        let pub_key = crypto.secp256k1_recover(&msg_digest, &signature, recovery_id);
-        // convert pub key to ethereum address (hash the raw pub key and take the last 20 bytes)
-        let recovered = EthAddress::from_sec1_pub_key(env, &pub_key);
-        // if the same address is used multiple times, fail
-        if recovered <= last {
-            return Err(Error::UnorderedOracles);
+        let index = if recovery_id & ED25519_SIGNATURE != 0 {
+            // ed25519 signatures reference the oracle by index, in ascending order
+            let index = recovery_id & !(ED25519_SIGNATURE | ED25519_PREVIOUS_EPOCH);
+            if last_index.is_some_and(|last_index| index <= last_index) {
+                return Err(Error::UnorderedOracles.into());
+            }
+            last_index = Some(index);
+            match config.oracles.get(index).map(|oracle| oracle.key) {
+                Some(OracleKey::Ed25519(public_key)) => {
+                    crypto.ed25519_verify(&public_key, msg_digest.as_ref(), &signature);
+                    Some(index)
+                }
+                _ => None,
+            }
+        } else {
+            let recovered =
+                recover_ordered_signer(env, &msg_digest, &signature, recovery_id, &last)?;
+            // check if the computed address is included in the permitted oracles list
+            let index = find_oracle(&config.oracles, &recovered.clone().into());
+            if index.is_some() {
+                last = recovered;
+            }
+            index
+        };
+        if let Some(index) = index {
+            signers.push_back(index);
         }
-        // check if the computed address is included in the permitted oracles list
-        if has_oracle(env, recovered.clone()) {
-            // address is a part of the permitted oracles list, increase verified signature counter
-            valid += 1;
-            // count valid signatures
-            last = recovered;
+    }
+    check_quorum(env, config, policy, &signers)
+}
+
+fn verify_config_signatures_indexed(
+    env: &Env,
+    epoch: u32,
+    config: &OracleConfig,
+    tx_input_hash: &impl Fn(&BytesN<32>) -> BytesN<32>,
+    signer_bitmap: u128,
+    signatures: &Vec<(BytesN<64>, u32)>,
+    policy: &VerificationPolicy,
+) -> Result<(), VerificationError> {
+    // the bitmap must reference existing oracles, with one signature each
+    if config.oracles.len() < 128 && signer_bitmap >> config.oracles.len() != 0 {
+        return Err(Error::InvalidSignerBitmap.into());
+    }
+    if signer_bitmap.count_ones() != signatures.len() {
+        return Err(Error::InvalidSignerBitmap.into());
+    }
+    let crypto = env.crypto();
+    let msg_digest = message_digest(env, epoch, config, tx_input_hash);
+    let mut signers: Vec<u32> = Vec::new(env);
+    let mut remaining = signer_bitmap;
+    for (signature, recovery_id) in signatures.iter() {
+        let index = remaining.trailing_zeros();
+        remaining &= remaining - 1;
+        // compare the signature with the expected oracle, this will panic on invalid ed25519
+        // signatures, the scheme flag has to match the oracle's before that
+        let ed25519 = recovery_id & ED25519_SIGNATURE != 0;
+        match config.oracles.get_unchecked(index).key {
+            OracleKey::Secp256k1(address) if !ed25519 => {
+                if recover_signer(env, &msg_digest, &signature, recovery_id)? != address {
+                    return Err(Error::UnauthorizedOracleSignatures.into());
+                }
+            }
+            OracleKey::Ed25519(public_key) if ed25519 => {
+                crypto.ed25519_verify(&public_key, msg_digest.as_ref(), &signature);
+            }
+            _ => return Err(Error::InvalidSignature.into()),
         }
+        signers.push_back(index);
     }
-    if valid < threshold {
-        return Err(Error::UnauthorizedOracleSignatures);
+    check_quorum(env, config, policy, &signers)
+}
+
+/// Dry run the verification of a randomness response against the active configuration, reporting
+/// the outcome of every signature instead of failing on the first problem.
+pub fn diagnose_signatures(
+    env: &Env,
+    id: &RequestId,
+    random_words: &Vec<BytesN<32>>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> SignatureDiagnostics {
+    diagnose_typed_signatures(
+        env,
+        |config_digest| tx_input_hash(env, id, random_words, config_digest),
+        signatures,
+    )
+}
+
+/// Same as `diagnose_signatures` for any typed data hash produced by `tx_input_hash`.
+pub fn diagnose_typed_signatures(
+    env: &Env,
+    tx_input_hash: impl Fn(&BytesN<32>) -> BytesN<32>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> SignatureDiagnostics {
+    let epoch = get_active_epoch(env);
+    let config = get_config(env, epoch).unwrap();
+    let config_digest = config_digest(env, epoch, &config);
+    let msg_digest = message_digest(env, epoch, &config, &tx_input_hash);
+    let mut diagnostics = Vec::new(env);
+    let mut weight: u32 = 0;
+    let mut last = EthAddress::zero(env);
+    let mut last_index: Option<u32> = None;
+    for (signature, recovery_id) in signatures.iter() {
+        let (signer, status, index, ordered) = if recovery_id & ED25519_SIGNATURE != 0 {
+            let index = recovery_id & !(ED25519_SIGNATURE | ED25519_PREVIOUS_EPOCH);
+            let ordered = last_index.map_or(true, |last_index| index > last_index);
+            last_index = Some(index);
+            match config.oracles.get(index).map(|oracle| oracle.key) {
+                Some(key @ OracleKey::Ed25519(_)) => {
+                    (key, SignatureStatus::Unverified, Some(index), ordered)
+                }
+                _ => (
+                    OracleKey::Ed25519(BytesN::from_array(env, &[0; 32])),
+                    SignatureStatus::Unverified,
+                    None,
+                    ordered,
+                ),
+            }
+        } else {
+            match recover_signer(env, &msg_digest, &signature, recovery_id) {
+                Ok(recovered) => {
+                    let ordered = recovered > last;
+                    let key: OracleKey = recovered.clone().into();
+                    let index = find_oracle(&config.oracles, &key);
+                    if index.is_some() && ordered {
+                        last = recovered;
+                    }
+                    (key, SignatureStatus::Valid, index, ordered)
+                }
+                Err(_) => (
+                    EthAddress::zero(env).into(),
+                    SignatureStatus::Invalid,
+                    None,
+                    false,
+                ),
+            }
+        };
+        if let (Some(index), true, SignatureStatus::Valid) = (index, ordered, status) {
+            weight += config.oracles.get_unchecked(index).weight;
+        }
+        diagnostics.push_back(SignatureDiagnostic {
+            signer,
+            status,
+            member: index.is_some(),
+            ordered,
+        });
+    }
+    SignatureDiagnostics {
+        epoch,
+        domain_separator: get_domain_separator(env),
+        config_digest,
+        msg_digest,
+        signatures: diagnostics,
+        weight,
+        threshold: config.threshold,
+    }
+}
+
+/// Check the weights and group minimums of the oracles which provided a valid signature, and the
+/// requirements of the policy.
+fn check_quorum(
+    env: &Env,
+    config: &OracleConfig,
+    policy: &VerificationPolicy,
+    signers: &Vec<u32>,
+) -> Result<(), VerificationError> {
+    let mut valid: u32 = 0;
+    let mut group_signers: Map<Symbol, u32> = Map::new(env);
+    for index in signers.iter() {
+        // add the weight of the permitted oracle to the verified signatures
+        let oracle = config.oracles.get_unchecked(index);
+        valid += oracle.weight;
+        for group in oracle.groups.iter() {
+            group_signers.set(group.clone(), group_signers.get(group).unwrap_or(0) + 1);
+        }
+    }
+    if valid < config.threshold.max(policy.min_threshold) {
+        return Err(Error::UnauthorizedOracleSignatures.into());
+    }
+    for (group, minimum) in config.group_minimums.iter() {
+        if group_signers.get(group.clone()).unwrap_or(0) < minimum {
+            return Err(VerificationError::GroupQuorumNotMet(group));
+        }
+    }
+    for oracle in policy.required_oracles.iter() {
+        let signed =
+            find_oracle(&config.oracles, &oracle).is_some_and(|index| signers.contains(index));
+        if !signed {
+            return Err(Error::RequiredOracleMissing.into());
+        }
     }
     Ok(())
 }
@@ -240,11 +1522,26 @@ mod test {
         println!("EIP712DOMAINTYPE_HASH: {}", hash_u8(&env, domain_prefix));
         println!("NAME_HASH: {}", hash_u8(&env, b"Relink MultiSig"));
         println!("VERSION_HASH: {}", hash_u8(&env, b"1"));
-        let signature_prefix = b"ProxyRequest(bytes32 requestId,uint256[] randomWords)";
+        let signature_prefix =
+            b"ProxyRequest(bytes32 requestId,uint256[] randomWords,bytes32 configDigest)";
         println!(
             "RANDOMNESS_RECEIVED_HASH: {}",
             hash_u8(&env, signature_prefix)
         );
+        println!(
+            "ORACLE_CONFIG_HASH: {}",
+            hash_u8(
+                &env,
+                b"OracleConfig(uint32 epoch,bytes32[] oracles,uint32[] weights,bytes32[] groups,bytes32 groupMinimums,uint32 threshold)"
+            )
+        );
+        println!(
+            "CONFIG_UPDATE_HASH: {}",
+            hash_u8(
+                &env,
+                b"ConfigUpdate(address[] oracles,uint32 threshold,uint64 nonce,bytes32 configDigest)"
+            )
+        );
         println!(
             "Network ID: Futurenet = {}",
             hash_u8(&env, b"Test SDF Future Network ; October 2022")
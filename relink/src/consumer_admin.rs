@@ -0,0 +1,811 @@
+use soroban_sdk::{contractclient, Address, BytesN, Env, Map, Symbol, Vec};
+
+use crate::consumer::{DomainConfig, OracleConfig, OracleEntry, OracleKey};
+use crate::eip712::Domain;
+use crate::{confirmed_owner, consumer, Error, EthAddress};
+
+#[contractclient(name = "VrfConsumerAdminClient")]
+pub trait VrfConsumerAdmin {
+    /// Add an oracle to the permitted oracles. Must be authorized by the owner.
+    fn add_oracle(env: Env, oracle: EthAddress) -> Result<(), Error>;
+
+    /// Remove an oracle from the permitted oracles. Must be authorized by the owner.
+    fn remove_oracle(env: Env, oracle: EthAddress) -> Result<(), Error>;
+
+    /// Add an oracle of any signature scheme to the permitted oracles. Must be authorized by the
+    /// owner.
+    fn add_oracle_key(env: Env, oracle: OracleKey) -> Result<(), Error>;
+
+    /// Remove an oracle of any signature scheme from the permitted oracles. Must be authorized by
+    /// the owner.
+    fn remove_oracle_key(env: Env, oracle: OracleKey) -> Result<(), Error>;
+
+    /// Update the weight of an oracle's signature. Must be authorized by the owner.
+    fn set_oracle_weight(env: Env, oracle: OracleKey, weight: u32) -> Result<(), Error>;
+
+    /// Replace the labels of the groups an oracle belongs to. Must be authorized by the owner.
+    fn set_oracle_groups(env: Env, oracle: OracleKey, groups: Vec<Symbol>) -> Result<(), Error>;
+
+    /// Require at least `minimum` oracles of the group to sign, zero removes the rule. Must be
+    /// authorized by the owner.
+    fn set_group_minimum(env: Env, group: Symbol, minimum: u32) -> Result<(), Error>;
+
+    /// Update the number of oracle signatures required. Must be authorized by the owner.
+    fn set_threshold(env: Env, threshold: u32) -> Result<(), Error>;
+
+    /// Update the proxy contract allowed to deliver responses. Must be authorized by the owner.
+    fn set_proxy(env: Env, proxy: Address);
+
+    /// Schedule a new oracle configuration, returns its epoch. Must be authorized by the owner.
+    fn set_oracle_config(
+        env: Env,
+        oracles: Vec<EthAddress>,
+        threshold: u32,
+        activation_ledger: u32,
+    ) -> Result<u32, Error>;
+
+    /// Schedule a new configuration with weighted oracles, returns its epoch. Must be authorized
+    /// by the owner.
+    fn set_weighted_oracle_config(
+        env: Env,
+        oracles: Vec<OracleEntry>,
+        threshold: u32,
+        group_minimums: Map<Symbol, u32>,
+        activation_ledger: u32,
+    ) -> Result<u32, Error>;
+
+    /// Update the number of ledgers the previous configuration is accepted after a scheduled
+    /// rotation, `consumer::DEFAULT_OVERLAP_WINDOW` unless set. Changes to single oracles or the
+    /// threshold always end the previous configuration. Must be authorized by the owner.
+    fn set_overlap_window(env: Env, ledgers: u32);
+
+    /// Update the number of ledgers after a request during which its response is accepted, zero
+    /// disables the deadline. At most `pending_requests::REQUEST_TTL`. Must be authorized by the
+    /// owner.
+    fn set_max_fulfillment_delay(env: Env, ledgers: u32) -> Result<(), Error>;
+
+    /// Switch the EIP-712 domain the oracles sign with, signatures for the previous domain are no
+    /// longer accepted. Must be authorized by the owner.
+    fn set_domain(env: Env, domain: DomainConfig);
+
+    /// Allow a threshold of zero, which disables the signature verification. Development only.
+    /// Must be authorized by the owner.
+    fn set_insecure_mode(env: Env, enabled: bool) -> Result<(), Error>;
+
+    /// Return the number of oracle signatures required.
+    fn get_threshold(env: Env) -> u32;
+
+    /// Return the oracle configuration of the epoch, if it was not pruned yet.
+    fn get_oracle_config(env: Env, epoch: u32) -> Option<OracleConfig>;
+
+    /// Return the epoch of the configuration currently used for verification.
+    fn get_active_epoch(env: Env) -> u32;
+
+    /// Return the config digest the oracles of the active configuration have to sign.
+    fn get_config_digest(env: Env) -> BytesN<32>;
+
+    /// Check whether the oracle is one of the permitted oracles.
+    fn is_oracle(env: Env, oracle: EthAddress) -> bool;
+
+    /// Check whether the oracle of any signature scheme is one of the permitted oracles.
+    fn is_oracle_key(env: Env, oracle: OracleKey) -> bool;
+
+    /// Return the number of permitted oracles.
+    fn get_oracle_count(env: Env) -> u32;
+
+    /// Return the weight of the oracle's signature, zero if it is not permitted.
+    fn get_oracle_weight(env: Env, oracle: OracleKey) -> u32;
+
+    /// Return the minimum number of signers per oracle group.
+    fn get_group_minimums(env: Env) -> Map<Symbol, u32>;
+
+    /// Check whether a threshold of zero is allowed.
+    fn is_insecure_mode(env: Env) -> bool;
+
+    /// Return the number of ledgers after a request during which its response is accepted.
+    fn get_max_fulfillment_delay(env: Env) -> u32;
+
+    /// Return the EIP-712 domain the oracles have to sign with.
+    fn get_domain(env: Env) -> Domain;
+
+    /// Return the separator of the EIP-712 domain the oracles have to sign with.
+    fn get_domain_separator(env: Env) -> BytesN<32>;
+
+    /// Return the proxy contract allowed to deliver responses.
+    fn get_proxy(env: Env) -> Address;
+}
+
+pub fn add_oracle(env: &Env, oracle: EthAddress) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::add_oracle(env, oracle)
+}
+
+pub fn remove_oracle(env: &Env, oracle: EthAddress) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::remove_oracle(env, oracle)
+}
+
+pub fn add_oracle_key(env: &Env, oracle: OracleKey) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::add_oracle_key(env, oracle)
+}
+
+pub fn remove_oracle_key(env: &Env, oracle: OracleKey) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::remove_oracle_key(env, oracle)
+}
+
+pub fn set_oracle_weight(env: &Env, oracle: OracleKey, weight: u32) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_oracle_weight(env, oracle, weight)
+}
+
+pub fn set_oracle_groups(env: &Env, oracle: OracleKey, groups: Vec<Symbol>) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_oracle_groups(env, oracle, groups)
+}
+
+pub fn set_group_minimum(env: &Env, group: Symbol, minimum: u32) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_group_minimum(env, group, minimum)
+}
+
+pub fn set_threshold(env: &Env, threshold: u32) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_threshold(env, threshold)
+}
+
+pub fn set_oracle_config(
+    env: &Env,
+    oracles: Vec<EthAddress>,
+    threshold: u32,
+    activation_ledger: u32,
+) -> Result<u32, Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_oracle_config(env, oracles, threshold, activation_ledger)
+}
+
+pub fn set_weighted_oracle_config(
+    env: &Env,
+    oracles: Vec<OracleEntry>,
+    threshold: u32,
+    group_minimums: Map<Symbol, u32>,
+    activation_ledger: u32,
+) -> Result<u32, Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_weighted_oracle_config(env, oracles, threshold, group_minimums, activation_ledger)
+}
+
+pub fn set_overlap_window(env: &Env, ledgers: u32) {
+    confirmed_owner::require_owner(env);
+    consumer::set_overlap_window(env, ledgers);
+}
+
+pub fn set_max_fulfillment_delay(env: &Env, ledgers: u32) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_max_fulfillment_delay(env, ledgers)
+}
+
+pub fn set_domain(env: &Env, domain: &DomainConfig) {
+    confirmed_owner::require_owner(env);
+    consumer::set_domain(env, domain);
+}
+
+pub fn set_insecure_mode(env: &Env, enabled: bool) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    consumer::set_insecure_mode(env, enabled)
+}
+
+pub fn set_proxy(env: &Env, proxy: &Address) {
+    confirmed_owner::require_owner(env);
+    consumer::set_proxy(env, proxy);
+}
+
+#[macro_export]
+macro_rules! impl_vrf_consumer_admin {
+    ($type:ident) => {
+        #[contractimpl]
+        impl consumer_admin::VrfConsumerAdmin for $type {
+            /// Add an oracle to the permitted oracles. Must be authorized by the owner.
+            fn add_oracle(env: Env, oracle: EthAddress) -> Result<(), $crate::Error> {
+                consumer_admin::add_oracle(&env, oracle)
+            }
+
+            /// Remove an oracle from the permitted oracles. Must be authorized by the owner.
+            fn remove_oracle(env: Env, oracle: EthAddress) -> Result<(), $crate::Error> {
+                consumer_admin::remove_oracle(&env, oracle)
+            }
+
+            /// Add an oracle of any signature scheme to the permitted oracles. Must be authorized
+            /// by the owner.
+            fn add_oracle_key(env: Env, oracle: consumer::OracleKey) -> Result<(), $crate::Error> {
+                consumer_admin::add_oracle_key(&env, oracle)
+            }
+
+            /// Remove an oracle of any signature scheme from the permitted oracles. Must be
+            /// authorized by the owner.
+            fn remove_oracle_key(
+                env: Env,
+                oracle: consumer::OracleKey,
+            ) -> Result<(), $crate::Error> {
+                consumer_admin::remove_oracle_key(&env, oracle)
+            }
+
+            /// Update the weight of an oracle's signature. Must be authorized by the owner.
+            fn set_oracle_weight(
+                env: Env,
+                oracle: consumer::OracleKey,
+                weight: u32,
+            ) -> Result<(), $crate::Error> {
+                consumer_admin::set_oracle_weight(&env, oracle, weight)
+            }
+
+            /// Replace the labels of the groups an oracle belongs to. Must be authorized by the
+            /// owner.
+            fn set_oracle_groups(
+                env: Env,
+                oracle: consumer::OracleKey,
+                groups: soroban_sdk::Vec<soroban_sdk::Symbol>,
+            ) -> Result<(), $crate::Error> {
+                consumer_admin::set_oracle_groups(&env, oracle, groups)
+            }
+
+            /// Require at least `minimum` oracles of the group to sign, zero removes the rule.
+            /// Must be authorized by the owner.
+            fn set_group_minimum(
+                env: Env,
+                group: soroban_sdk::Symbol,
+                minimum: u32,
+            ) -> Result<(), $crate::Error> {
+                consumer_admin::set_group_minimum(&env, group, minimum)
+            }
+
+            /// Update the number of oracle signatures required. Must be authorized by the owner.
+            fn set_threshold(env: Env, threshold: u32) -> Result<(), $crate::Error> {
+                consumer_admin::set_threshold(&env, threshold)
+            }
+
+            /// Update the proxy contract allowed to deliver responses. Must be authorized by the owner.
+            fn set_proxy(env: Env, proxy: Address) {
+                consumer_admin::set_proxy(&env, &proxy)
+            }
+
+            /// Schedule a new oracle configuration, returns its epoch. Must be authorized by the owner.
+            fn set_oracle_config(
+                env: Env,
+                oracles: soroban_sdk::Vec<EthAddress>,
+                threshold: u32,
+                activation_ledger: u32,
+            ) -> Result<u32, $crate::Error> {
+                consumer_admin::set_oracle_config(&env, oracles, threshold, activation_ledger)
+            }
+
+            /// Schedule a new configuration with weighted oracles, returns its epoch. Must be
+            /// authorized by the owner.
+            fn set_weighted_oracle_config(
+                env: Env,
+                oracles: soroban_sdk::Vec<consumer::OracleEntry>,
+                threshold: u32,
+                group_minimums: soroban_sdk::Map<soroban_sdk::Symbol, u32>,
+                activation_ledger: u32,
+            ) -> Result<u32, $crate::Error> {
+                consumer_admin::set_weighted_oracle_config(
+                    &env,
+                    oracles,
+                    threshold,
+                    group_minimums,
+                    activation_ledger,
+                )
+            }
+
+            /// Update the number of ledgers the previous configuration is accepted after a rotation.
+            /// Must be authorized by the owner.
+            fn set_overlap_window(env: Env, ledgers: u32) {
+                consumer_admin::set_overlap_window(&env, ledgers)
+            }
+
+            /// Update the number of ledgers after a request during which its response is
+            /// accepted, zero disables the deadline. At most `pending_requests::REQUEST_TTL`. Must be
+            /// authorized by the owner.
+            fn set_max_fulfillment_delay(env: Env, ledgers: u32) -> Result<(), $crate::Error> {
+                consumer_admin::set_max_fulfillment_delay(&env, ledgers)
+            }
+
+            /// Switch the EIP-712 domain the oracles sign with, signatures for the previous domain
+            /// are no longer accepted. Must be authorized by the owner.
+            fn set_domain(env: Env, domain: consumer::DomainConfig) {
+                consumer_admin::set_domain(&env, &domain)
+            }
+
+            /// Allow a threshold of zero, which disables the signature verification. Development
+            /// only. Must be authorized by the owner.
+            fn set_insecure_mode(env: Env, enabled: bool) -> Result<(), $crate::Error> {
+                consumer_admin::set_insecure_mode(&env, enabled)
+            }
+
+            /// Return the number of oracle signatures required.
+            fn get_threshold(env: Env) -> u32 {
+                consumer::get_threshold(&env)
+            }
+
+            /// Return the oracle configuration of the epoch, if it was not pruned yet.
+            fn get_oracle_config(env: Env, epoch: u32) -> Option<consumer::OracleConfig> {
+                consumer::get_config(&env, epoch)
+            }
+
+            /// Return the epoch of the configuration currently used for verification.
+            fn get_active_epoch(env: Env) -> u32 {
+                consumer::get_active_epoch(&env)
+            }
+
+            /// Return the config digest the oracles of the active configuration have to sign.
+            fn get_config_digest(env: Env) -> soroban_sdk::BytesN<32> {
+                consumer::get_config_digest(&env)
+            }
+
+            /// Check whether the oracle is one of the permitted oracles.
+            fn is_oracle(env: Env, oracle: EthAddress) -> bool {
+                consumer::has_oracle(&env, oracle)
+            }
+
+            /// Check whether the oracle of any signature scheme is one of the permitted oracles.
+            fn is_oracle_key(env: Env, oracle: consumer::OracleKey) -> bool {
+                consumer::has_oracle_key(&env, oracle)
+            }
+
+            /// Return the number of permitted oracles.
+            fn get_oracle_count(env: Env) -> u32 {
+                consumer::get_oracle_count(&env)
+            }
+
+            /// Return the weight of the oracle's signature, zero if it is not permitted.
+            fn get_oracle_weight(env: Env, oracle: consumer::OracleKey) -> u32 {
+                consumer::get_oracle_weight(&env, oracle)
+            }
+
+            /// Return the minimum number of signers per oracle group.
+            fn get_group_minimums(env: Env) -> soroban_sdk::Map<soroban_sdk::Symbol, u32> {
+                consumer::get_group_minimums(&env)
+            }
+
+            /// Check whether a threshold of zero is allowed.
+            fn is_insecure_mode(env: Env) -> bool {
+                consumer::is_insecure_mode(&env)
+            }
+
+            /// Return the number of ledgers after a request during which its response is accepted.
+            fn get_max_fulfillment_delay(env: Env) -> u32 {
+                consumer::get_max_fulfillment_delay(&env)
+            }
+
+            /// Return the EIP-712 domain the oracles have to sign with.
+            fn get_domain(env: Env) -> $crate::eip712::Domain {
+                consumer::get_domain(&env)
+            }
+
+            /// Return the separator of the EIP-712 domain the oracles have to sign with.
+            fn get_domain_separator(env: Env) -> soroban_sdk::BytesN<32> {
+                consumer::get_domain_separator(&env)
+            }
+
+            /// Return the proxy contract allowed to deliver responses.
+            fn get_proxy(env: Env) -> Address {
+                consumer::get_proxy(&env)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use soroban_sdk::testutils::{
+        Address as AddressTestTrait, AuthorizedFunction, AuthorizedInvocation, Events,
+    };
+    use soroban_sdk::{contract, contractimpl, map, vec, BytesN, IntoVal, Map, Symbol, Vec};
+
+    use crate::testutils::{advance_ledger_sequence, TestOracleGenerator};
+    use crate::{confirmed_owner, consumer, consumer_admin, event_decoder, events};
+
+    use super::*;
+
+    #[contract]
+    struct TestContract;
+
+    #[contractimpl]
+    impl TestContract {
+        pub fn init(
+            env: Env,
+            owner: Address,
+            proxy: Address,
+            threshold: u32,
+            oracles: Vec<EthAddress>,
+        ) -> Result<(), Error> {
+            confirmed_owner::init(&env, &owner);
+            consumer::init(&env, &proxy, threshold, oracles, None)
+        }
+    }
+
+    impl_vrf_consumer_admin!(TestContract);
+
+    fn oracle(env: &Env, byte: u8) -> EthAddress {
+        EthAddress::from_bytes(BytesN::from_array(env, &[byte; 20]))
+    }
+
+    fn key(env: &Env, byte: u8) -> OracleKey {
+        oracle(env, byte).into()
+    }
+
+    fn setup<'a>() -> (Env, Address, TestContractClient<'a>) {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        // Create and initialize the test contract
+        let contract_address = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract_address);
+        let owner = Address::generate(&env);
+        let proxy = Address::generate(&env);
+        client.init(&owner, &proxy, &1, &vec![&env, oracle(&env, 1)]);
+
+        (env, owner, client)
+    }
+
+    #[test]
+    fn views() {
+        let (env, _, client) = setup();
+        assert_eq!(client.get_threshold(), 1);
+        assert!(client.is_oracle(&oracle(&env, 1)));
+        assert!(!client.is_oracle(&oracle(&env, 2)));
+    }
+
+    #[test]
+    fn manage_oracles() {
+        let (env, _, client) = setup();
+        client.add_oracle(&oracle(&env, 2));
+        client.add_oracle(&oracle(&env, 3));
+        client.remove_oracle(&oracle(&env, 1));
+        client.set_threshold(&2);
+        let proxy = Address::generate(&env);
+        client.set_proxy(&proxy);
+
+        assert!(!client.is_oracle(&oracle(&env, 1)));
+        assert!(client.is_oracle(&oracle(&env, 2)));
+        assert_eq!(client.get_oracle_count(), 2);
+        assert_eq!(client.get_threshold(), 2);
+        assert_eq!(client.get_proxy(), proxy);
+    }
+
+    #[test]
+    fn validation() {
+        let (env, _, client) = setup();
+        // the threshold must be reachable and not zero
+        assert_eq!(
+            client.try_set_threshold(&2),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        assert_eq!(
+            client.try_set_threshold(&0),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        assert_eq!(
+            client.try_remove_oracle(&oracle(&env, 1)),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        // oracles must be unique
+        assert_eq!(
+            client.try_add_oracle(&oracle(&env, 1)),
+            Err(Ok(Error::DuplicateOracle))
+        );
+        assert_eq!(
+            client.try_remove_oracle(&oracle(&env, 2)),
+            Err(Ok(Error::OracleUnknown))
+        );
+        let sequence = env.ledger().sequence();
+        assert_eq!(
+            client.try_set_oracle_config(
+                &vec![&env, oracle(&env, 2), oracle(&env, 2)],
+                &1,
+                &sequence
+            ),
+            Err(Ok(Error::DuplicateOracle))
+        );
+        // nothing was changed
+        assert_eq!(client.get_active_epoch(), 1);
+        assert_eq!(client.get_oracle_count(), 1);
+    }
+
+    #[test]
+    fn weights() {
+        let (env, _, client) = setup();
+        client.add_oracle(&oracle(&env, 2));
+        assert_eq!(client.get_oracle_weight(&key(&env, 2)), 1);
+        assert_eq!(client.get_oracle_weight(&key(&env, 3)), 0);
+        // the threshold is limited by the total weight
+        assert_eq!(
+            client.try_set_threshold(&3),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        client.set_oracle_weight(&key(&env, 2), &2);
+        client.set_threshold(&3);
+        assert_eq!(client.get_oracle_weight(&key(&env, 2)), 2);
+        assert_eq!(
+            client.try_set_oracle_weight(&key(&env, 2), &0),
+            Err(Ok(Error::InvalidWeight))
+        );
+        assert_eq!(
+            client.try_set_oracle_weight(&key(&env, 3), &1),
+            Err(Ok(Error::OracleUnknown))
+        );
+        let sequence = env.ledger().sequence();
+        client.set_weighted_oracle_config(
+            &vec![
+                &env,
+                OracleEntry {
+                    key: key(&env, 3),
+                    weight: 5,
+                    groups: Vec::new(&env),
+                },
+            ],
+            &5,
+            &Map::new(&env),
+            &sequence,
+        );
+        assert_eq!(client.get_oracle_weight(&key(&env, 3)), 5);
+        assert_eq!(client.get_threshold(), 5);
+    }
+
+    #[test]
+    fn groups() {
+        let (env, _, client) = setup();
+        let relink = Symbol::new(&env, "relink");
+        client.add_oracle(&oracle(&env, 2));
+        // a group minimum requires enough members
+        assert_eq!(
+            client.try_set_group_minimum(&relink, &1),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        // an oracle cannot be counted twice for the same group
+        assert_eq!(
+            client
+                .try_set_oracle_groups(&key(&env, 1), &vec![&env, relink.clone(), relink.clone()]),
+            Err(Ok(Error::DuplicateGroup))
+        );
+        client.set_oracle_groups(&key(&env, 1), &vec![&env, relink.clone()]);
+        client.set_group_minimum(&relink, &1);
+        assert_eq!(client.get_group_minimums(), map![&env, (relink.clone(), 1)]);
+        // groups and group minimums are part of the digest the oracles sign
+        let epoch = client.get_active_epoch();
+        let config = client.get_oracle_config(&epoch).unwrap();
+        let mut ungrouped = config.clone();
+        let mut entry = ungrouped.oracles.get(0).unwrap();
+        entry.groups = Vec::new(&env);
+        ungrouped.oracles.set(0, entry);
+        let mut unrestricted = config.clone();
+        unrestricted.group_minimums = Map::new(&env);
+        let digest = consumer::config_digest(&env, epoch, &config);
+        assert_ne!(consumer::config_digest(&env, epoch, &ungrouped), digest);
+        assert_ne!(consumer::config_digest(&env, epoch, &unrestricted), digest);
+        // the last member of a group with a minimum cannot leave it
+        assert_eq!(
+            client.try_set_oracle_groups(&key(&env, 1), &Vec::new(&env)),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        let config = client
+            .get_oracle_config(&client.get_active_epoch())
+            .unwrap();
+        assert_eq!(
+            config.oracles.get(0).unwrap().groups,
+            vec![&env, relink.clone()]
+        );
+        // a minimum of zero removes the rule
+        client.set_group_minimum(&relink, &0);
+        assert!(client.get_group_minimums().is_empty());
+    }
+
+    #[test]
+    fn insecure_mode() {
+        let (env, _, client) = setup();
+        assert!(!client.is_insecure_mode());
+        client.set_insecure_mode(&true);
+        assert!(client.is_insecure_mode());
+        client.set_threshold(&0);
+        // insecure mode cannot be left while signatures are not required
+        assert_eq!(
+            client.try_set_insecure_mode(&false),
+            Err(Ok(Error::InvalidThreshold))
+        );
+        // raising the threshold ends the configuration without signatures right away
+        client.set_threshold(&1);
+        assert_eq!(client.get_oracle_config(&2), None);
+        let verify = || {
+            env.as_contract(&client.address, || {
+                consumer::verify_signatures(&env, |digest| digest.clone(), &Vec::new(&env))
+            })
+        };
+        assert_eq!(verify(), Err(Error::TooFewSignatures));
+        client.set_insecure_mode(&false);
+        assert!(!client.is_insecure_mode());
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            event_decoder::decode(&env, &topics, &data),
+            Ok(event_decoder::RelinkEvent::InsecureModeSet(
+                events::InsecureModeSetEvent {
+                    version: events::EVENT_VERSION,
+                    enabled: false,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn domain() {
+        let (env, _, client) = setup();
+        assert_eq!(client.get_domain(), Domain::relink(&env, &client.address));
+        let separator = client.get_domain_separator();
+        let config = DomainConfig {
+            name: soroban_sdk::String::from_str(&env, "Dice"),
+            version: soroban_sdk::String::from_str(&env, "2"),
+            salt: BytesN::from_array(&env, &[7; 32]),
+        };
+        client.set_domain(&config);
+        let domain = Domain {
+            name: config.name,
+            version: config.version,
+            salt: config.salt,
+            verifying_contract: client.address.clone(),
+        };
+        assert_eq!(client.get_domain(), domain);
+        assert_ne!(client.get_domain_separator(), separator);
+        assert_eq!(client.get_domain_separator(), domain.separator(&env));
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            event_decoder::decode(&env, &topics, &data),
+            Ok(event_decoder::RelinkEvent::DomainSet(
+                events::DomainSetEvent {
+                    version: events::EVENT_VERSION,
+                    domain: domain.clone(),
+                    domain_separator: domain.separator(&env),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn scheduled_config() {
+        let (env, _, client) = setup();
+        let oracles = vec![&env, oracle(&env, 2), oracle(&env, 3)];
+        let sequence = env.ledger().sequence();
+        assert_eq!(client.set_oracle_config(&oracles, &2, &(sequence + 10)), 2);
+        // configurations are activated in order and never in the past
+        assert_eq!(
+            client.try_set_oracle_config(&oracles, &2, &(sequence + 5)),
+            Err(Ok(Error::InvalidActivationLedger))
+        );
+        advance_ledger_sequence(&env, 1);
+        assert_eq!(
+            client.try_set_oracle_config(&oracles, &2, &sequence),
+            Err(Ok(Error::InvalidActivationLedger))
+        );
+        // the new configuration is not active before the activation ledger
+        assert_eq!(client.get_active_epoch(), 1);
+        assert!(client.is_oracle(&oracle(&env, 1)));
+        advance_ledger_sequence(&env, 9);
+        assert_eq!(client.get_active_epoch(), 2);
+        assert!(!client.is_oracle(&oracle(&env, 1)));
+        // the previous configuration is only accepted if the owner opens an overlap window
+        env.as_contract(&client.address, || {
+            assert_eq!(consumer::get_overlap_window(&env), 0)
+        });
+        assert_eq!(client.get_threshold(), 2);
+        assert_eq!(
+            client.get_oracle_config(&2),
+            Some(consumer::OracleConfig {
+                oracles: vec![
+                    &env,
+                    OracleEntry::new(&env, oracle(&env, 2)),
+                    OracleEntry::new(&env, oracle(&env, 3))
+                ],
+                threshold: 2,
+                group_minimums: Map::new(&env),
+                activation_ledger: sequence + 10,
+            })
+        );
+        let config = client.get_oracle_config(&2).unwrap();
+        assert_eq!(
+            client.get_config_digest(),
+            consumer::config_digest(&env, 2, &config)
+        );
+        // configurations no longer usable for verification are pruned, and the one replaced by
+        // an update is dropped right away
+        client.set_threshold(&1);
+        assert_eq!(client.get_oracle_config(&1), None);
+        assert_eq!(client.get_oracle_config(&2), None);
+        assert_eq!(client.get_active_epoch(), 3);
+    }
+
+    #[test]
+    fn removed_oracle() {
+        let (env, _, client) = setup();
+        let oracles = TestOracleGenerator::new().generate_sorted(&env, 2);
+        client.add_oracle(&oracles[0].address(&env));
+        client.add_oracle(&oracles[1].address(&env));
+        // an overlap window for scheduled rotations does not apply to single changes
+        client.set_overlap_window(&720);
+        let verify = |signer: usize, config_digest: &BytesN<32>| {
+            let signature = oracles[signer].sign_typed(&env, &client.address, config_digest);
+            env.as_contract(&client.address, || {
+                consumer::verify_signatures(&env, |digest| digest.clone(), &vec![&env, signature])
+            })
+        };
+        let digest = client.get_config_digest();
+        assert_eq!(verify(0, &digest), Ok(()));
+
+        // a signature from the removed oracle is rejected in the next ledger
+        client.remove_oracle(&oracles[0].address(&env));
+        advance_ledger_sequence(&env, 1);
+        assert_eq!(verify(0, &digest), Err(Error::UnauthorizedOracleSignatures));
+        assert_eq!(
+            verify(0, &client.get_config_digest()),
+            Err(Error::UnauthorizedOracleSignatures)
+        );
+        assert_eq!(verify(1, &client.get_config_digest()), Ok(()));
+
+        // a scheduled configuration changed before its activation is never accepted
+        let sequence = env.ledger().sequence();
+        let all = vec![&env, oracles[0].address(&env), oracles[1].address(&env)];
+        let scheduled = client.set_oracle_config(&all, &1, &(sequence + 10));
+        let scheduled_digest = consumer::config_digest(
+            &env,
+            scheduled,
+            &client.get_oracle_config(&scheduled).unwrap(),
+        );
+        client.remove_oracle(&oracles[0].address(&env));
+        assert_eq!(client.get_oracle_config(&scheduled), None);
+        assert_eq!(client.get_active_epoch(), scheduled - 1);
+        advance_ledger_sequence(&env, 10);
+        assert_eq!(client.get_active_epoch(), scheduled + 1);
+        assert_eq!(
+            verify(0, &scheduled_digest),
+            Err(Error::UnauthorizedOracleSignatures)
+        );
+        assert_eq!(verify(1, &client.get_config_digest()), Ok(()));
+    }
+
+    #[test]
+    fn authorization() {
+        let (env, owner, client) = setup();
+        client.set_threshold(&1);
+        // verify that the owner's signature was checked
+        assert_eq!(
+            env.auths(),
+            std::vec![(
+                // Address for which authorization check is performed
+                owner.clone(),
+                // Invocation tree that needs to be authorized
+                AuthorizedInvocation {
+                    // Function that is authorized. Can be a contract function or a host function that requires authorization.
+                    function: AuthorizedFunction::Contract((
+                        // Address of the called contract
+                        client.address.clone(),
+                        // Name of the called function
+                        Symbol::new(&env, "set_threshold"),
+                        // Arguments used to call `set_threshold` (converted to the env-managed vector via `into_val`)
+                        (1_u32,).into_val(&env),
+                    )),
+                    // The contract doesn't call any other contracts that require authorization,
+                    sub_invocations: std::vec![]
+                }
+            )]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn unauthorized() {
+        let (env, _, client) = setup();
+        // drop the mocked authorizations
+        env.mock_auths(&[]);
+        client.add_oracle(&oracle(&env, 2));
+    }
+}
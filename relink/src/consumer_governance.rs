@@ -0,0 +1,205 @@
+use soroban_sdk::{contractclient, BytesN, Env, Vec};
+
+use crate::{Error, EthAddress};
+
+/// Governance of the oracle set by the oracles themselves, for consumers without an owner.
+#[contractclient(name = "VrfConsumerGovernanceClient")]
+pub trait VrfConsumerGovernance {
+    /// Replace the oracles, authorized by a threshold of the current oracles signing a
+    /// `ConfigUpdate` message with the current nonce. Returns the epoch of the new configuration.
+    /// Only configurations of equally weighted secp256k1 oracles without groups can be updated.
+    fn update_oracle_config(
+        env: Env,
+        oracles: Vec<EthAddress>,
+        threshold: u32,
+        nonce: u64,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<u32, Error>;
+
+    /// Return the nonce the next `ConfigUpdate` message has to be signed with.
+    fn get_config_update_nonce(env: Env) -> u64;
+}
+
+#[macro_export]
+macro_rules! impl_vrf_consumer_governance {
+    ($type:ident) => {
+        #[contractimpl]
+        impl consumer_governance::VrfConsumerGovernance for $type {
+            /// Replace the oracles, authorized by a threshold of the current oracles signing a
+            /// `ConfigUpdate` message with the current nonce. Returns the epoch of the new
+            /// configuration.
+            fn update_oracle_config(
+                env: Env,
+                oracles: soroban_sdk::Vec<$crate::EthAddress>,
+                threshold: u32,
+                nonce: u64,
+                signatures: soroban_sdk::Vec<(soroban_sdk::BytesN<64>, u32)>,
+            ) -> Result<u32, $crate::Error> {
+                $crate::consumer::update_oracle_config(&env, oracles, threshold, nonce, &signatures)
+            }
+
+            /// Return the nonce the next `ConfigUpdate` message has to be signed with.
+            fn get_config_update_nonce(env: Env) -> u64 {
+                $crate::consumer::get_config_update_nonce(&env)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_sdk::testutils::{Address as AddressTestTrait, Ledger};
+    use soroban_sdk::{contract, contractimpl, vec, Address, Map};
+
+    use crate::testutils::{TestOracle, TestOracleGenerator};
+    use crate::{consumer, consumer_governance};
+
+    use super::*;
+
+    #[contract]
+    struct TestContract;
+
+    #[contractimpl]
+    impl TestContract {
+        pub fn init(
+            env: Env,
+            proxy: Address,
+            threshold: u32,
+            oracles: Vec<EthAddress>,
+        ) -> Result<(), Error> {
+            consumer::init(&env, &proxy, threshold, oracles, None)
+        }
+
+        pub fn is_oracle(env: Env, oracle: EthAddress) -> bool {
+            consumer::has_oracle(&env, oracle)
+        }
+    }
+
+    impl_vrf_consumer_governance!(TestContract);
+
+    fn addresses(env: &Env, oracles: &[TestOracle]) -> Vec<EthAddress> {
+        let mut addresses = Vec::new(env);
+        for oracle in oracles {
+            addresses.push_back(oracle.address(env));
+        }
+        addresses
+    }
+
+    #[test]
+    fn update_oracle_config() {
+        let env = Env::default();
+        let contract_address = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract_address);
+        let oracles = TestOracleGenerator::new().generate_sorted(&env, 5);
+        client.init(
+            &Address::generate(&env),
+            &2,
+            &addresses(&env, &oracles[..3]),
+        );
+
+        // rotate to the last three oracles
+        let new_oracles = addresses(&env, &oracles[2..]);
+        let sign = |signers: &[usize], nonce: u64| {
+            let mut signatures = Vec::new(&env);
+            for index in signers {
+                signatures.push_back(oracles[*index].sign_config_update(
+                    &env,
+                    &contract_address,
+                    &new_oracles,
+                    2,
+                    nonce,
+                ));
+            }
+            signatures
+        };
+        assert_eq!(client.get_config_update_nonce(), 0);
+        // the nonce has to match
+        assert_eq!(
+            client.try_update_oracle_config(&new_oracles, &2, &1, &sign(&[0, 1], 1)),
+            Err(Ok(Error::InvalidNonce))
+        );
+        // a threshold of the current oracles has to sign
+        assert_eq!(
+            client.try_update_oracle_config(&new_oracles, &2, &0, &sign(&[0, 3], 0)),
+            Err(Ok(Error::UnauthorizedOracleSignatures))
+        );
+        let signatures = sign(&[0, 1], 0);
+        assert_eq!(
+            client.update_oracle_config(&new_oracles, &2, &0, &signatures),
+            2
+        );
+        assert_eq!(client.get_config_update_nonce(), 1);
+        assert!(!client.is_oracle(&oracles[0].address(&env)));
+        assert!(client.is_oracle(&oracles[4].address(&env)));
+        // signatures cannot be replayed
+        assert_eq!(
+            client.try_update_oracle_config(&new_oracles, &2, &0, &signatures),
+            Err(Ok(Error::InvalidNonce))
+        );
+    }
+
+    #[test]
+    fn unsupported_config() {
+        let env = Env::default();
+        let contract_address = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract_address);
+        let oracles = TestOracleGenerator::new().generate_sorted(&env, 3);
+        client.init(
+            &Address::generate(&env),
+            &1,
+            &addresses(&env, &oracles[..2]),
+        );
+        let new_oracles = addresses(&env, &oracles[2..]);
+        let signatures = vec![
+            &env,
+            oracles[0].sign_config_update(&env, &contract_address, &new_oracles, 1, 0),
+        ];
+
+        // a scheduled configuration would be replaced
+        let sequence = env.ledger().sequence();
+        env.as_contract(&contract_address, || {
+            consumer::set_oracle_config(&env, addresses(&env, &oracles[..2]), 1, sequence + 10)
+                .unwrap()
+        });
+        assert_eq!(
+            client.try_update_oracle_config(&new_oracles, &1, &0, &signatures),
+            Err(Ok(Error::UnsupportedConfig))
+        );
+
+        // weights would be dropped
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 10);
+        env.as_contract(&contract_address, || {
+            let mut entries = Vec::new(&env);
+            for oracle in addresses(&env, &oracles[..2]).iter() {
+                let mut entry = consumer::OracleEntry::new(&env, oracle);
+                entry.weight = 2;
+                entries.push_back(entry);
+            }
+            consumer::set_weighted_oracle_config(&env, entries, 2, Map::new(&env), sequence + 10)
+                .unwrap()
+        });
+        assert_eq!(
+            client.try_update_oracle_config(&new_oracles, &1, &0, &signatures),
+            Err(Ok(Error::UnsupportedConfig))
+        );
+    }
+
+    #[test]
+    fn insecure_mode() {
+        let env = Env::default();
+        let contract_address = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract_address);
+        env.as_contract(&contract_address, || {
+            consumer::init_insecure(&env, &Address::generate(&env)).unwrap()
+        });
+        // nobody can govern a consumer without a threshold
+        let oracles = vec![
+            &env,
+            TestOracleGenerator::new().generate_sorted(&env, 1)[0].address(&env),
+        ];
+        assert_eq!(
+            client.try_update_oracle_config(&oracles, &1, &0, &Vec::new(&env)),
+            Err(Ok(Error::UnauthorizedOracleSignatures))
+        );
+    }
+}
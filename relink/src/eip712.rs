@@ -0,0 +1,485 @@
+//! Building blocks for EIP-712 typed data signed by Relink oracles: the signing domain, an
+//! `abi.encode` builder for struct hashes, secp256k1 signature recovery and a threshold check of
+//! the signatures.
+use soroban_sdk::{bytes, bytesn, contracttype, Address, Bytes, BytesN, Env, String, Vec};
+
+use crate::utils::address_bytes;
+use crate::{Error, EthAddress};
+
+/// Keccak256 of "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract,bytes32 salt)"
+const DOMAIN_TYPE_HASH: [u8; 32] = [
+    0xd8, 0x7c, 0xd6, 0xef, 0x79, 0xd4, 0xe2, 0xb9, 0x5e, 0x15, 0xce, 0x8a, 0xbf, 0x73, 0x2d, 0xb5,
+    0x1e, 0xc7, 0x71, 0xf1, 0xca, 0x2e, 0xdc, 0xcf, 0x22, 0xa4, 0x6c, 0x72, 0x9a, 0xc5, 0x64, 0x72,
+];
+
+/// Maximum length of the strings of a domain.
+const MAX_STRING_LENGTH: u32 = 64;
+
+/// Order of the secp256k1 curve.
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Half the order of the secp256k1 curve, the maximum `s` value of a signature.
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Signing domain, the chain id is the network id of the ledger.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Domain {
+    pub name: String,
+    pub version: String,
+    pub salt: BytesN<32>,
+    pub verifying_contract: Address,
+}
+
+impl Domain {
+    /// The domain Relink oracles sign with by default.
+    pub fn relink(env: &Env, verifying_contract: &Address) -> Self {
+        Self {
+            name: String::from_str(env, "Relink MultiSig"),
+            version: String::from_str(env, "1"),
+            salt: bytesn!(
+                env,
+                0x151543af6b722378665a73fe38dbceae4871a070b7cdaf5c6e30cf758dc33cc8
+            ),
+            verifying_contract: verifying_contract.clone(),
+        }
+    }
+
+    /// Implement Solidity equivalent domain separator:
+    /// domainSeparator = keccak256(
+    ///     abi.encode(
+    ///         EIP712DOMAINTYPE_HASH,
+    ///         keccak256(name),
+    ///         keccak256(version),
+    ///         block.chainid,
+    ///         address(this),
+    ///         salt
+    ///     )
+    /// );
+    pub fn separator(&self, env: &Env) -> BytesN<32> {
+        Encoder::new(env, &BytesN::from_array(env, &DOMAIN_TYPE_HASH))
+            .string(&self.name)
+            .string(&self.version)
+            .bytes32(&env.ledger().network_id())
+            .stellar_address(&self.verifying_contract)
+            .bytes32(&self.salt)
+            .hash()
+    }
+}
+
+/// Builder for `keccak256(abi.encode(typeHash, ...))`. Static values are encoded as 32 byte words,
+/// dynamic values and arrays by the hash of their contents as EIP-712 requires.
+pub struct Encoder {
+    env: Env,
+    buffer: Bytes,
+}
+
+impl Encoder {
+    /// Start encoding a struct of the type with the given hash.
+    pub fn new(env: &Env, type_hash: &BytesN<32>) -> Self {
+        Self {
+            env: env.clone(),
+            buffer: Bytes::from_array(env, &type_hash.to_array()),
+        }
+    }
+
+    pub fn bytes32(mut self, value: &BytesN<32>) -> Self {
+        self.buffer.append(value.as_ref());
+        self
+    }
+
+    pub fn uint32(self, value: u32) -> Self {
+        self.uint128(value as u128)
+    }
+
+    pub fn uint64(self, value: u64) -> Self {
+        self.uint128(value as u128)
+    }
+
+    pub fn uint128(mut self, value: u128) -> Self {
+        self.buffer.append(&Bytes::from_array(&self.env, &[0; 16]));
+        self.buffer
+            .append(&Bytes::from_array(&self.env, &value.to_be_bytes()));
+        self
+    }
+
+    pub fn bool(self, value: bool) -> Self {
+        self.uint128(value as u128)
+    }
+
+    /// Encode a `uint32[]` by the hash of its concatenated, left-padded elements.
+    pub fn uint32_array(self, values: &Vec<u32>) -> Self {
+        let mut buffer = Bytes::new(&self.env);
+        for value in values.iter() {
+            let mut word = [0u8; 32];
+            word[28..].copy_from_slice(&value.to_be_bytes());
+            buffer.append(&Bytes::from_array(&self.env, &word));
+        }
+        self.bytes(&buffer)
+    }
+
+    /// Encode an Ethereum address, left-padded to 32 bytes.
+    pub fn address(mut self, value: &EthAddress) -> Self {
+        self.buffer.append(&Bytes::from_array(&self.env, &[0; 12]));
+        self.buffer.append(value.as_bytes().as_ref());
+        self
+    }
+
+    /// Encode the 32 raw bytes of a Stellar address.
+    pub fn stellar_address(mut self, value: &Address) -> Self {
+        self.buffer.append(&address_bytes(&self.env, value));
+        self
+    }
+
+    /// Encode dynamic `bytes` by their hash.
+    pub fn bytes(self, value: &Bytes) -> Self {
+        let hash = self.env.crypto().keccak256(value);
+        self.bytes32(&hash)
+    }
+
+    /// Encode a `string` by its hash. Strings are limited to 64 bytes.
+    pub fn string(self, value: &String) -> Self {
+        let len = value.len();
+        if len > MAX_STRING_LENGTH {
+            panic!("string too long");
+        }
+        let mut buffer = [0u8; MAX_STRING_LENGTH as usize];
+        value.copy_into_slice(&mut buffer[..len as usize]);
+        let bytes = Bytes::from_slice(&self.env, &buffer[..len as usize]);
+        self.bytes(&bytes)
+    }
+
+    /// Encode a `bytes32[]` by the hash of its concatenated elements.
+    pub fn bytes32_array(self, values: &Vec<BytesN<32>>) -> Self {
+        let mut buffer = Bytes::new(&self.env);
+        for value in values.iter() {
+            buffer.append(value.as_ref());
+        }
+        self.bytes(&buffer)
+    }
+
+    /// Encode an `address[]` by the hash of its concatenated, left-padded elements.
+    pub fn address_array(self, values: &Vec<EthAddress>) -> Self {
+        let mut buffer = Bytes::new(&self.env);
+        for value in values.iter() {
+            buffer.append(&Bytes::from_array(&self.env, &[0; 12]));
+            buffer.append(value.as_bytes().as_ref());
+        }
+        self.bytes(&buffer)
+    }
+
+    /// Encode a nested struct by its struct hash, as produced by another `Encoder`.
+    pub fn struct_hash(self, value: &BytesN<32>) -> Self {
+        self.bytes32(value)
+    }
+
+    /// Finish encoding and return the struct hash.
+    pub fn hash(self) -> BytesN<32> {
+        self.env.crypto().keccak256(&self.buffer)
+    }
+}
+
+/// Return the digest the oracles sign: `keccak256("\x19\x01" || domainSeparator || structHash)`.
+pub fn typed_data_digest(
+    env: &Env,
+    domain_separator: &BytesN<32>,
+    struct_hash: &BytesN<32>,
+) -> BytesN<32> {
+    let mut msg = bytes!(env, 0x1901);
+    msg.append(domain_separator.as_ref());
+    msg.append(struct_hash.as_ref());
+    env.crypto().keccak256(&msg)
+}
+
+/// Verify that at least `threshold` of the oracles signed the typed data. The recovered addresses
+/// have to be in ascending order, signatures of other addresses are ignored. Meant for contracts
+/// with a plain list of secp256k1 signers, consumers verify against their weighted configuration
+/// with `consumer::verify_signatures`. A threshold of zero is rejected.
+pub fn verify_threshold_signatures(
+    env: &Env,
+    domain_separator: &BytesN<32>,
+    struct_hash: &BytesN<32>,
+    oracles: &Vec<EthAddress>,
+    threshold: u32,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    if threshold == 0 {
+        return Err(Error::InvalidThreshold);
+    }
+    if signatures.len() < threshold {
+        return Err(Error::TooFewSignatures);
+    }
+    let msg_digest = typed_data_digest(env, domain_separator, struct_hash);
+    let mut valid: u32 = 0;
+    let mut last = EthAddress::zero(env);
+    for (signature, recovery_id) in signatures.iter() {
+        let recovered = recover_ordered_signer(env, &msg_digest, &signature, recovery_id, &last)?;
+        if oracles.contains(&recovered) {
+            valid += 1;
+            last = recovered;
+        }
+    }
+    if valid < threshold {
+        return Err(Error::UnauthorizedOracleSignatures);
+    }
+    Ok(())
+}
+
+/// Same as `recover_signer`, but the address has to come after `last`, so that signers are listed
+/// in ascending order and none of them is counted twice.
+pub fn recover_ordered_signer(
+    env: &Env,
+    msg_digest: &BytesN<32>,
+    signature: &BytesN<64>,
+    recovery_id: u32,
+    last: &EthAddress,
+) -> Result<EthAddress, Error> {
+    let recovered = recover_signer(env, msg_digest, signature, recovery_id)?;
+    if recovered <= *last {
+        return Err(Error::UnorderedOracles);
+    }
+    Ok(recovered)
+}
+
+/// Recover the address which produced a secp256k1 signature. Ethereum style recovery ids of 27
+/// and 28 are accepted, signatures with a high `s` value or out of range components are rejected
+/// with `InvalidSignature`. An `r` in range which is not the x coordinate of a curve point still
+/// traps the host.
+pub fn recover_signer(
+    env: &Env,
+    msg_digest: &BytesN<32>,
+    signature: &BytesN<64>,
+    recovery_id: u32,
+) -> Result<EthAddress, Error> {
+    let recovery_id = normalize_recovery_id(recovery_id)?;
+    let bytes = signature.to_array();
+    let r: [u8; 32] = bytes[..32].try_into().unwrap();
+    let s: [u8; 32] = bytes[32..].try_into().unwrap();
+    let zero = [0u8; 32];
+    if r == zero || r >= SECP256K1_ORDER || s == zero || s > SECP256K1_HALF_ORDER {
+        return Err(Error::InvalidSignature);
+    }
+    let pub_key = env
+        .crypto()
+        .secp256k1_recover(msg_digest, signature, recovery_id);
+    // convert pub key to ethereum address (hash the raw pub key and take the last 20 bytes)
+    Ok(EthAddress::from_sec1_pub_key(env, &pub_key))
+}
+
+/// Map a recovery id of 0, 1, 27 or 28 to 0 or 1.
+pub fn normalize_recovery_id(recovery_id: u32) -> Result<u32, Error> {
+    match recovery_id {
+        0 | 1 => Ok(recovery_id),
+        27 | 28 => Ok(recovery_id - 27),
+        _ => Err(Error::InvalidSignature),
+    }
+}
+
+/// Convert a 65 byte `r || s || v` signature or a 64 byte EIP-2098 compact `r || yParityAndS`
+/// signature to the `(r || s, recovery id)` format used for verification.
+pub fn parse_signature(env: &Env, signature: &Bytes) -> Result<(BytesN<64>, u32), Error> {
+    match signature.len() {
+        65 => {
+            let rs: BytesN<64> = signature.slice(..64).try_into().unwrap();
+            let v = signature.get_unchecked(64) as u32;
+            Ok((rs, normalize_recovery_id(v)?))
+        }
+        64 => {
+            // the highest bit of s holds the parity of y
+            let mut bytes = [0u8; 64];
+            signature.copy_into_slice(&mut bytes);
+            let recovery_id = (bytes[32] >> 7) as u32;
+            bytes[32] &= 0x7f;
+            Ok((BytesN::from_array(env, &bytes), recovery_id))
+        }
+        _ => Err(Error::InvalidSignature),
+    }
+}
+
+/// Convert a list of signatures with `parse_signature`.
+pub fn parse_signatures(
+    env: &Env,
+    signatures: &Vec<Bytes>,
+) -> Result<Vec<(BytesN<64>, u32)>, Error> {
+    let mut parsed = Vec::new(env);
+    for signature in signatures.iter() {
+        parsed.push_back(parse_signature(env, &signature)?);
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_sdk::testutils::Address as AddressTestTrait;
+    use soroban_sdk::{vec, Address, Bytes, BytesN, Env, String};
+
+    use crate::testutils::{hash_u8, TestOracle, TestOracleGenerator};
+    use crate::Error;
+
+    use super::*;
+
+    #[test]
+    fn domain_type_hash() {
+        let env = Env::default();
+        assert_eq!(
+            hash_u8(&env, b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract,bytes32 salt)"),
+            crate::testutils::hash_hex(&BytesN::from_array(&env, &DOMAIN_TYPE_HASH))
+        );
+    }
+
+    #[test]
+    fn encoder() {
+        let env = Env::default();
+        let type_hash = BytesN::from_array(&env, &[1; 32]);
+        let address = EthAddress::from_bytes(BytesN::from_array(&env, &[2; 20]));
+        let hash = Encoder::new(&env, &type_hash)
+            .uint32(7)
+            .address(&address)
+            .string(&String::from_str(&env, "abc"))
+            .hash();
+
+        // abi.encode(typeHash, uint32(7), address, keccak256("abc"))
+        let mut expected = Bytes::from_array(&env, &[1; 32]);
+        let mut word = [0u8; 32];
+        word[31] = 7;
+        expected.append(&Bytes::from_array(&env, &word));
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(&[2; 20]);
+        expected.append(&Bytes::from_array(&env, &word));
+        expected.append(
+            env.crypto()
+                .keccak256(&Bytes::from_slice(&env, b"abc"))
+                .as_ref(),
+        );
+        assert_eq!(hash, env.crypto().keccak256(&expected));
+        // arrays are encoded by the hash of their elements
+        let words = vec![&env, type_hash.clone(), type_hash.clone()];
+        let mut concatenated = Bytes::from_array(&env, &[1; 32]);
+        concatenated.append(&Bytes::from_array(&env, &[1; 32]));
+        assert_eq!(
+            Encoder::new(&env, &type_hash).bytes32_array(&words).hash(),
+            Encoder::new(&env, &type_hash).bytes(&concatenated).hash()
+        );
+    }
+
+    #[test]
+    fn threshold_signatures() {
+        let env = Env::default();
+        let oracles = TestOracleGenerator::new().generate_sorted(&env, 3);
+        let members = vec![&env, oracles[0].address(&env), oracles[1].address(&env)];
+        let domain_separator = Domain::relink(&env, &Address::generate(&env)).separator(&env);
+        let struct_hash = BytesN::from_array(&env, &[3; 32]);
+        let digest = typed_data_digest(&env, &domain_separator, &struct_hash);
+        let sign = |index: usize| oracles[index].sign_digest(&env, &digest);
+        let verify = |signatures| {
+            verify_threshold_signatures(
+                &env,
+                &domain_separator,
+                &struct_hash,
+                &members,
+                2,
+                &signatures,
+            )
+        };
+
+        assert_eq!(verify(vec![&env, sign(0), sign(1)]), Ok(()));
+        // without a threshold no signature would be needed
+        assert_eq!(
+            verify_threshold_signatures(
+                &env,
+                &domain_separator,
+                &struct_hash,
+                &members,
+                0,
+                &Vec::new(&env),
+            ),
+            Err(Error::InvalidThreshold)
+        );
+        assert_eq!(verify(vec![&env, sign(0)]), Err(Error::TooFewSignatures));
+        assert_eq!(
+            verify(vec![&env, sign(1), sign(0)]),
+            Err(Error::UnorderedOracles)
+        );
+        assert_eq!(
+            verify(vec![&env, sign(0), sign(2)]),
+            Err(Error::UnauthorizedOracleSignatures)
+        );
+    }
+
+    #[test]
+    fn signature_formats() {
+        let env = Env::default();
+        let oracle = TestOracle::new(&[1; 32]);
+        let digest = BytesN::from_array(&env, &[2; 32]);
+        let (signature, recovery_id) = oracle.sign_digest(&env, &digest);
+        assert_eq!(
+            recover_signer(&env, &digest, &signature, recovery_id),
+            Ok(oracle.address(&env))
+        );
+
+        // ethereum style recovery ids
+        assert_eq!(normalize_recovery_id(27), Ok(0));
+        assert_eq!(normalize_recovery_id(28), Ok(1));
+        assert_eq!(normalize_recovery_id(2), Err(Error::InvalidSignature));
+        assert_eq!(
+            recover_signer(&env, &digest, &signature, recovery_id + 27),
+            Ok(oracle.address(&env))
+        );
+        assert_eq!(
+            recover_signer(&env, &digest, &signature, 4),
+            Err(Error::InvalidSignature)
+        );
+
+        // r || s || v
+        let mut raw = Bytes::from_array(&env, &signature.to_array());
+        raw.push_back(recovery_id as u8 + 27);
+        assert_eq!(
+            parse_signature(&env, &raw),
+            Ok((signature.clone(), recovery_id))
+        );
+        // EIP-2098 compact r || yParityAndS
+        let mut compact = signature.to_array();
+        compact[32] |= (recovery_id as u8) << 7;
+        assert_eq!(
+            parse_signature(&env, &Bytes::from_array(&env, &compact)),
+            Ok((signature.clone(), recovery_id))
+        );
+        assert_eq!(
+            parse_signature(&env, &Bytes::from_array(&env, &[0; 63])),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn signature_malleability() {
+        let env = Env::default();
+        let oracle = TestOracle::new(&[1; 32]);
+        let digest = BytesN::from_array(&env, &[2; 32]);
+        let (signature, recovery_id) = oracle.sign_digest(&env, &digest);
+
+        // the mirrored signature (r, n - s) with the flipped recovery id is rejected
+        let mut bytes = signature.to_array();
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let difference = SECP256K1_ORDER[i] as i16 - bytes[32 + i] as i16 - borrow;
+            bytes[32 + i] = difference.rem_euclid(256) as u8;
+            borrow = (difference < 0) as i16;
+        }
+        let mirrored = BytesN::from_array(&env, &bytes);
+        assert_eq!(
+            recover_signer(&env, &digest, &mirrored, recovery_id ^ 1),
+            Err(Error::InvalidSignature)
+        );
+        // zero components are rejected instead of trapping
+        assert_eq!(
+            recover_signer(&env, &digest, &BytesN::from_array(&env, &[0; 64]), 0),
+            Err(Error::InvalidSignature)
+        );
+    }
+}
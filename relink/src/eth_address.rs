@@ -14,7 +14,7 @@ impl EthAddress {
         // drop the type-byte of sec-1 encoding
         let raw_pub_key = &pub_key.as_ref().slice(1..);
         // hash the raw public key
-        let hash = env.crypto().keccak256(&raw_pub_key);
+        let hash = env.crypto().keccak256(raw_pub_key);
         // use the last 20 bytes as the address
         EthAddress(hash.as_ref().slice(12..).try_into().unwrap_optimized())
     }
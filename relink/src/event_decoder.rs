@@ -0,0 +1,881 @@
+//! Decoder for the events described in [crate::events], for use by off-chain indexers.
+#![cfg(any(test, feature = "std"))]
+extern crate std;
+
+use core::fmt::{Display, Formatter};
+
+use soroban_sdk::xdr::{ContractEvent, ContractEventBody};
+use soroban_sdk::{Env, Map, Symbol, TryFromVal, Val, Vec};
+
+use crate::events::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelinkEvent {
+    OwnershipTransferRequested(OwnershipTransferRequestedEvent),
+    OwnershipTransferred(OwnershipTransferredEvent),
+    ThresholdSet(ThresholdSetEvent),
+    OracleAdded(OracleAddedEvent),
+    OracleRemoved(OracleRemovedEvent),
+    Ed25519OracleAdded(Ed25519OracleAddedEvent),
+    Ed25519OracleRemoved(Ed25519OracleRemovedEvent),
+    ProxySet(ProxySetEvent),
+    RandomnessRequested(RandomnessRequestedEvent),
+    RandomnessProvided(RandomnessProvidedEvent),
+    RandomnessReceived(RandomnessReceivedEvent),
+    RequestCancelled(RequestCancelledEvent),
+    CallbackFailed(CallbackFailedEvent),
+    FeeSet(FeeSetEvent),
+    WhitelistAdded(WhitelistAddedEvent),
+    WhitelistRemoved(WhitelistRemovedEvent),
+    Withdrawn(WithdrawnEvent),
+    PausedSet(PausedSetEvent),
+    OracleConfigSet(OracleConfigSetEvent),
+    OverlapWindowSet(OverlapWindowSetEvent),
+    MaxFulfillmentDelaySet(MaxFulfillmentDelaySetEvent),
+    DomainSet(DomainSetEvent),
+    ResultRetentionSet(ResultRetentionSetEvent),
+    PoolConfigSet(PoolConfigSetEvent),
+    PoolRefilled(PoolRefilledEvent),
+    RandomnessDrawn(RandomnessDrawnEvent),
+    TargetAdded(TargetAddedEvent),
+    TargetRemoved(TargetRemovedEvent),
+    RandomnessForwarded(RandomnessForwardedEvent),
+    FunctionsRequested(FunctionsRequestedEvent),
+    FunctionsResponded(FunctionsRespondedEvent),
+    UpkeepRegistered(UpkeepRegisteredEvent),
+    UpkeepFunded(UpkeepFundedEvent),
+    UpkeepPerformed(UpkeepPerformedEvent),
+    UpkeepCancelled(UpkeepCancelledEvent),
+    MessageReceived(MessageReceivedEvent),
+    MessageSkipped(MessageSkippedEvent),
+    ReceiverSet(ReceiverSetEvent),
+    ReceiverRemoved(ReceiverRemovedEvent),
+    InsecureModeSet(InsecureModeSetEvent),
+    OracleWeightSet(OracleWeightSetEvent),
+    OracleGroupsSet(OracleGroupsSetEvent),
+    GroupMinimumSet(GroupMinimumSetEvent),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The first topic is missing or not a symbol.
+    MissingName,
+    /// The event name is not part of the schema.
+    UnknownEvent,
+    /// The event data does not match the schema.
+    InvalidPayload,
+    /// The event was published with a schema version this decoder does not support.
+    UnsupportedVersion(u32),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::MissingName => write!(f, "missing event name"),
+            DecodeError::UnknownEvent => write!(f, "unknown event"),
+            DecodeError::InvalidPayload => write!(f, "invalid event payload"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported event version: {version}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decode an event from its topics and data.
+pub fn decode(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<RelinkEvent, DecodeError> {
+    let name = topics
+        .get(0)
+        .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+        .ok_or(DecodeError::MissingName)?;
+    // check the schema version before decoding the payload
+    let fields =
+        Map::<Symbol, Val>::try_from_val(env, data).map_err(|_| DecodeError::InvalidPayload)?;
+    let version = fields
+        .get(Symbol::new(env, "version"))
+        .and_then(|version| u32::try_from_val(env, &version).ok())
+        .ok_or(DecodeError::InvalidPayload)?;
+    if version != EVENT_VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+    let event = if name == OWNERSHIP_TRANSFER_REQUESTED {
+        RelinkEvent::OwnershipTransferRequested(payload(env, &fields, data, &["from", "to"])?)
+    } else if name == OWNERSHIP_TRANSFERRED {
+        RelinkEvent::OwnershipTransferred(payload(env, &fields, data, &["from", "to"])?)
+    } else if name == THRESHOLD_SET {
+        RelinkEvent::ThresholdSet(payload(env, &fields, data, &["threshold"])?)
+    } else if name == ORACLE_ADDED {
+        RelinkEvent::OracleAdded(payload(env, &fields, data, &["oracle"])?)
+    } else if name == ORACLE_REMOVED {
+        RelinkEvent::OracleRemoved(payload(env, &fields, data, &["oracle"])?)
+    } else if name == ED25519_ORACLE_ADDED {
+        RelinkEvent::Ed25519OracleAdded(payload(env, &fields, data, &["public_key"])?)
+    } else if name == ED25519_ORACLE_REMOVED {
+        RelinkEvent::Ed25519OracleRemoved(payload(env, &fields, data, &["public_key"])?)
+    } else if name == PROXY_SET {
+        RelinkEvent::ProxySet(payload(env, &fields, data, &["proxy"])?)
+    } else if name == RANDOMNESS_REQUESTED {
+        RelinkEvent::RandomnessRequested(payload(
+            env,
+            &fields,
+            data,
+            &[
+                "origin",
+                "dapp",
+                "nonce",
+                "id",
+                "request_confirmations",
+                "num_words",
+            ],
+        )?)
+    } else if name == RANDOMNESS_PROVIDED {
+        RelinkEvent::RandomnessProvided(payload(
+            env,
+            &fields,
+            data,
+            &["id", "backend", "random_words"],
+        )?)
+    } else if name == RANDOMNESS_RECEIVED {
+        RelinkEvent::RandomnessReceived(payload(env, &fields, data, &["id", "random_words"])?)
+    } else if name == REQUEST_CANCELLED {
+        RelinkEvent::RequestCancelled(payload(env, &fields, data, &["id"])?)
+    } else if name == CALLBACK_FAILED {
+        RelinkEvent::CallbackFailed(payload(env, &fields, data, &["id", "backend"])?)
+    } else if name == FEE_SET {
+        RelinkEvent::FeeSet(payload(env, &fields, data, &["fee"])?)
+    } else if name == WHITELIST_ADDED {
+        RelinkEvent::WhitelistAdded(payload(env, &fields, data, &["address"])?)
+    } else if name == WHITELIST_REMOVED {
+        RelinkEvent::WhitelistRemoved(payload(env, &fields, data, &["address"])?)
+    } else if name == WITHDRAWN {
+        RelinkEvent::Withdrawn(payload(env, &fields, data, &["token", "to", "amount"])?)
+    } else if name == PAUSED_SET {
+        RelinkEvent::PausedSet(payload(env, &fields, data, &["paused"])?)
+    } else if name == ORACLE_CONFIG_SET {
+        RelinkEvent::OracleConfigSet(payload(
+            env,
+            &fields,
+            data,
+            &[
+                "epoch",
+                "oracles",
+                "threshold",
+                "group_minimums",
+                "activation_ledger",
+                "config_digest",
+            ],
+        )?)
+    } else if name == OVERLAP_WINDOW_SET {
+        RelinkEvent::OverlapWindowSet(payload(env, &fields, data, &["ledgers"])?)
+    } else if name == MAX_FULFILLMENT_DELAY_SET {
+        RelinkEvent::MaxFulfillmentDelaySet(payload(env, &fields, data, &["ledgers"])?)
+    } else if name == DOMAIN_SET {
+        RelinkEvent::DomainSet(payload(
+            env,
+            &fields,
+            data,
+            &["domain", "domain_separator"],
+        )?)
+    } else if name == RESULT_RETENTION_SET {
+        RelinkEvent::ResultRetentionSet(payload(env, &fields, data, &["retention", "ttl"])?)
+    } else if name == POOL_CONFIG_SET {
+        RelinkEvent::PoolConfigSet(payload(
+            env,
+            &fields,
+            data,
+            &[
+                "token",
+                "draw_fee",
+                "low_water_mark",
+                "refill_words",
+                "refill_fee",
+            ],
+        )?)
+    } else if name == POOL_REFILLED {
+        RelinkEvent::PoolRefilled(payload(
+            env,
+            &fields,
+            data,
+            &["dapp", "id", "count", "available"],
+        )?)
+    } else if name == RANDOMNESS_DRAWN {
+        RelinkEvent::RandomnessDrawn(payload(
+            env,
+            &fields,
+            data,
+            &["dapp", "first_index", "random_words", "fee"],
+        )?)
+    } else if name == TARGET_ADDED {
+        RelinkEvent::TargetAdded(payload(env, &fields, data, &["target"])?)
+    } else if name == TARGET_REMOVED {
+        RelinkEvent::TargetRemoved(payload(env, &fields, data, &["target"])?)
+    } else if name == RANDOMNESS_FORWARDED {
+        RelinkEvent::RandomnessForwarded(payload(
+            env,
+            &fields,
+            data,
+            &["id", "target", "random_words"],
+        )?)
+    } else if name == FUNCTIONS_REQUESTED {
+        RelinkEvent::FunctionsRequested(payload(
+            env,
+            &fields,
+            data,
+            &["origin", "dapp", "nonce", "id", "payload"],
+        )?)
+    } else if name == FUNCTIONS_RESPONDED {
+        RelinkEvent::FunctionsResponded(payload(env, &fields, data, &["id", "response", "err"])?)
+    } else if name == UPKEEP_REGISTERED {
+        RelinkEvent::UpkeepRegistered(payload(
+            env,
+            &fields,
+            data,
+            &["id", "admin", "target", "interval", "max_fee"],
+        )?)
+    } else if name == UPKEEP_FUNDED {
+        RelinkEvent::UpkeepFunded(payload(env, &fields, data, &["id", "amount"])?)
+    } else if name == UPKEEP_PERFORMED {
+        RelinkEvent::UpkeepPerformed(payload(env, &fields, data, &["id", "backend", "fee"])?)
+    } else if name == UPKEEP_CANCELLED {
+        RelinkEvent::UpkeepCancelled(payload(env, &fields, data, &["id", "refund"])?)
+    } else if name == MESSAGE_RECEIVED {
+        RelinkEvent::MessageReceived(payload(
+            env,
+            &fields,
+            data,
+            &["source_chain", "sender", "nonce", "receiver"],
+        )?)
+    } else if name == MESSAGE_SKIPPED {
+        RelinkEvent::MessageSkipped(payload(
+            env,
+            &fields,
+            data,
+            &["source_chain", "sender", "nonce"],
+        )?)
+    } else if name == RECEIVER_SET {
+        RelinkEvent::ReceiverSet(payload(
+            env,
+            &fields,
+            data,
+            &["source_chain", "sender", "receiver"],
+        )?)
+    } else if name == RECEIVER_REMOVED {
+        RelinkEvent::ReceiverRemoved(payload(env, &fields, data, &["source_chain", "sender"])?)
+    } else if name == INSECURE_MODE_SET {
+        RelinkEvent::InsecureModeSet(payload(env, &fields, data, &["enabled"])?)
+    } else if name == ORACLE_WEIGHT_SET {
+        RelinkEvent::OracleWeightSet(payload(env, &fields, data, &["oracle", "weight"])?)
+    } else if name == ORACLE_GROUPS_SET {
+        RelinkEvent::OracleGroupsSet(payload(env, &fields, data, &["oracle", "groups"])?)
+    } else if name == GROUP_MINIMUM_SET {
+        RelinkEvent::GroupMinimumSet(payload(env, &fields, data, &["group", "minimum"])?)
+    } else {
+        return Err(DecodeError::UnknownEvent);
+    };
+    Ok(event)
+}
+
+/// Decode an event as returned in XDR by the RPC.
+pub fn decode_xdr(env: &Env, event: &ContractEvent) -> Result<RelinkEvent, DecodeError> {
+    let ContractEventBody::V0(body) = &event.body;
+    let mut topics = Vec::new(env);
+    for topic in body.topics.iter() {
+        topics.push_back(Val::try_from_val(env, topic).map_err(|_| DecodeError::InvalidPayload)?);
+    }
+    let data = Val::try_from_val(env, &body.data).map_err(|_| DecodeError::InvalidPayload)?;
+    decode(env, &topics, &data)
+}
+
+/// Decode the payload struct after making sure it has exactly the expected fields besides the
+/// version, the host does not fail gracefully on mismatching struct fields.
+fn payload<T: TryFromVal<Env, Val>>(
+    env: &Env,
+    fields: &Map<Symbol, Val>,
+    data: &Val,
+    names: &[&str],
+) -> Result<T, DecodeError> {
+    if fields.len() as usize != names.len() + 1 {
+        return Err(DecodeError::InvalidPayload);
+    }
+    for name in names {
+        if !fields.contains_key(Symbol::new(env, name)) {
+            return Err(DecodeError::InvalidPayload);
+        }
+    }
+    T::try_from_val(env, data).map_err(|_| DecodeError::InvalidPayload)
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use soroban_sdk::testutils::{Address as AddressTestTrait, Events};
+    use soroban_sdk::{
+        bytes, contract, contractimpl, map, symbol_short, vec, Address, BytesN, Env, IntoVal,
+        Symbol, Val,
+    };
+
+    use crate::consumer::{OracleEntry, OracleKey};
+    use crate::eip712::Domain;
+    use crate::event_decoder::{decode, DecodeError, RelinkEvent};
+    use crate::events;
+    use crate::events::*;
+    use crate::randomness_results::ResultRetention;
+    use crate::{EthAddress, RequestId};
+
+    #[contract]
+    struct TestContract;
+
+    #[contractimpl]
+    impl TestContract {
+        pub fn emit(env: Env, from: Address, to: Address) {
+            events::ownership_transfer_requested(&env, &from, &to);
+            events::threshold_set(&env, 3);
+        }
+    }
+
+    #[test]
+    fn decode_published() {
+        let env = Env::default();
+        let contract = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract);
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        client.emit(&from, &to);
+
+        let decoded: std::vec::Vec<RelinkEvent> = env
+            .events()
+            .all()
+            .iter()
+            .map(|(_, topics, data)| decode(&env, &topics, &data).unwrap())
+            .collect();
+        assert_eq!(
+            decoded,
+            std::vec![
+                RelinkEvent::OwnershipTransferRequested(OwnershipTransferRequestedEvent {
+                    version: EVENT_VERSION,
+                    from,
+                    to,
+                }),
+                RelinkEvent::ThresholdSet(ThresholdSetEvent {
+                    version: EVENT_VERSION,
+                    threshold: 3,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let env = Env::default();
+        let data = ThresholdSetEvent {
+            version: EVENT_VERSION,
+            threshold: 3,
+        }
+        .into_val(&env);
+        assert_eq!(
+            decode(&env, &vec![&env], &data),
+            Err(DecodeError::MissingName)
+        );
+        assert_eq!(
+            decode(
+                &env,
+                &vec![&env, symbol_short!("unknown").into_val(&env)],
+                &data
+            ),
+            Err(DecodeError::UnknownEvent)
+        );
+        assert_eq!(
+            decode(&env, &vec![&env, FEE_SET.into_val(&env)], &data),
+            Err(DecodeError::InvalidPayload)
+        );
+        let future = ThresholdSetEvent {
+            version: EVENT_VERSION + 1,
+            threshold: 3,
+        }
+        .into_val(&env);
+        assert_eq!(
+            decode(&env, &vec![&env, THRESHOLD_SET.into_val(&env)], &future),
+            Err(DecodeError::UnsupportedVersion(EVENT_VERSION + 1))
+        );
+    }
+
+    /// Decoding the payload of `event` published under `name` must return the same event, which
+    /// fails if the field names the decoder expects drift from the struct.
+    fn assert_round_trip<T: IntoVal<Env, Val> + Clone>(
+        env: &Env,
+        name: Symbol,
+        event: T,
+        variant: fn(T) -> RelinkEvent,
+    ) {
+        let data = event.clone().into_val(env);
+        assert_eq!(
+            decode(env, &vec![env, name.into_val(env)], &data),
+            Ok(variant(event))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let env = &Env::default();
+        let version = EVENT_VERSION;
+        let address = Address::generate(env);
+        let other = Address::generate(env);
+        let oracle = EthAddress::zero(env);
+        let public_key = BytesN::from_array(env, &[1; 32]);
+        let id = RequestId::new(env, &address, &other, &address, 1);
+        let words = vec![env, BytesN::from_array(env, &[7; 32])];
+        let group = symbol_short!("partners");
+        let key = OracleKey::Ed25519(public_key.clone());
+
+        assert_round_trip(
+            env,
+            OWNERSHIP_TRANSFER_REQUESTED,
+            OwnershipTransferRequestedEvent {
+                version,
+                from: address.clone(),
+                to: other.clone(),
+            },
+            RelinkEvent::OwnershipTransferRequested,
+        );
+        assert_round_trip(
+            env,
+            OWNERSHIP_TRANSFERRED,
+            OwnershipTransferredEvent {
+                version,
+                from: address.clone(),
+                to: other.clone(),
+            },
+            RelinkEvent::OwnershipTransferred,
+        );
+        assert_round_trip(
+            env,
+            THRESHOLD_SET,
+            ThresholdSetEvent {
+                version,
+                threshold: 2,
+            },
+            RelinkEvent::ThresholdSet,
+        );
+        assert_round_trip(
+            env,
+            ORACLE_ADDED,
+            OracleAddedEvent {
+                version,
+                oracle: oracle.clone(),
+            },
+            RelinkEvent::OracleAdded,
+        );
+        assert_round_trip(
+            env,
+            ORACLE_REMOVED,
+            OracleRemovedEvent {
+                version,
+                oracle: oracle.clone(),
+            },
+            RelinkEvent::OracleRemoved,
+        );
+        assert_round_trip(
+            env,
+            ED25519_ORACLE_ADDED,
+            Ed25519OracleAddedEvent {
+                version,
+                public_key: public_key.clone(),
+            },
+            RelinkEvent::Ed25519OracleAdded,
+        );
+        assert_round_trip(
+            env,
+            ED25519_ORACLE_REMOVED,
+            Ed25519OracleRemovedEvent {
+                version,
+                public_key: public_key.clone(),
+            },
+            RelinkEvent::Ed25519OracleRemoved,
+        );
+        assert_round_trip(
+            env,
+            PROXY_SET,
+            ProxySetEvent {
+                version,
+                proxy: address.clone(),
+            },
+            RelinkEvent::ProxySet,
+        );
+        assert_round_trip(
+            env,
+            RANDOMNESS_REQUESTED,
+            RandomnessRequestedEvent {
+                version,
+                origin: address.clone(),
+                dapp: other.clone(),
+                nonce: 1,
+                id: id.clone(),
+                request_confirmations: 2,
+                num_words: 1,
+            },
+            RelinkEvent::RandomnessRequested,
+        );
+        assert_round_trip(
+            env,
+            RANDOMNESS_PROVIDED,
+            RandomnessProvidedEvent {
+                version,
+                id: id.clone(),
+                backend: address.clone(),
+                random_words: words.clone(),
+            },
+            RelinkEvent::RandomnessProvided,
+        );
+        assert_round_trip(
+            env,
+            RANDOMNESS_RECEIVED,
+            RandomnessReceivedEvent {
+                version,
+                id: id.clone(),
+                random_words: words.clone(),
+            },
+            RelinkEvent::RandomnessReceived,
+        );
+        assert_round_trip(
+            env,
+            REQUEST_CANCELLED,
+            RequestCancelledEvent {
+                version,
+                id: id.clone(),
+            },
+            RelinkEvent::RequestCancelled,
+        );
+        assert_round_trip(
+            env,
+            CALLBACK_FAILED,
+            CallbackFailedEvent {
+                version,
+                id: id.clone(),
+                backend: address.clone(),
+            },
+            RelinkEvent::CallbackFailed,
+        );
+        assert_round_trip(
+            env,
+            FEE_SET,
+            FeeSetEvent { version, fee: 10 },
+            RelinkEvent::FeeSet,
+        );
+        assert_round_trip(
+            env,
+            WHITELIST_ADDED,
+            WhitelistAddedEvent {
+                version,
+                address: address.clone(),
+            },
+            RelinkEvent::WhitelistAdded,
+        );
+        assert_round_trip(
+            env,
+            WHITELIST_REMOVED,
+            WhitelistRemovedEvent {
+                version,
+                address: address.clone(),
+            },
+            RelinkEvent::WhitelistRemoved,
+        );
+        assert_round_trip(
+            env,
+            WITHDRAWN,
+            WithdrawnEvent {
+                version,
+                token: address.clone(),
+                to: other.clone(),
+                amount: 10,
+            },
+            RelinkEvent::Withdrawn,
+        );
+        assert_round_trip(
+            env,
+            PAUSED_SET,
+            PausedSetEvent {
+                version,
+                paused: true,
+            },
+            RelinkEvent::PausedSet,
+        );
+        assert_round_trip(
+            env,
+            ORACLE_CONFIG_SET,
+            OracleConfigSetEvent {
+                version,
+                epoch: 2,
+                oracles: vec![
+                    env,
+                    OracleEntry {
+                        key: key.clone(),
+                        weight: 1,
+                        groups: vec![env, group.clone()],
+                    },
+                ],
+                threshold: 1,
+                group_minimums: map![env, (group.clone(), 1)],
+                activation_ledger: 5,
+                config_digest: public_key.clone(),
+            },
+            RelinkEvent::OracleConfigSet,
+        );
+        assert_round_trip(
+            env,
+            OVERLAP_WINDOW_SET,
+            OverlapWindowSetEvent {
+                version,
+                ledgers: 10,
+            },
+            RelinkEvent::OverlapWindowSet,
+        );
+        assert_round_trip(
+            env,
+            MAX_FULFILLMENT_DELAY_SET,
+            MaxFulfillmentDelaySetEvent {
+                version,
+                ledgers: 10,
+            },
+            RelinkEvent::MaxFulfillmentDelaySet,
+        );
+        assert_round_trip(
+            env,
+            DOMAIN_SET,
+            DomainSetEvent {
+                version,
+                domain: Domain::relink(env, &address),
+                domain_separator: public_key.clone(),
+            },
+            RelinkEvent::DomainSet,
+        );
+        assert_round_trip(
+            env,
+            RESULT_RETENTION_SET,
+            ResultRetentionSetEvent {
+                version,
+                retention: ResultRetention::Persistent,
+                ttl: 100,
+            },
+            RelinkEvent::ResultRetentionSet,
+        );
+        assert_round_trip(
+            env,
+            POOL_CONFIG_SET,
+            PoolConfigSetEvent {
+                version,
+                token: address.clone(),
+                draw_fee: 1,
+                low_water_mark: 2,
+                refill_words: 3,
+                refill_fee: 4,
+            },
+            RelinkEvent::PoolConfigSet,
+        );
+        assert_round_trip(
+            env,
+            POOL_REFILLED,
+            PoolRefilledEvent {
+                version,
+                dapp: other.clone(),
+                id: id.clone(),
+                count: 3,
+                available: 5,
+            },
+            RelinkEvent::PoolRefilled,
+        );
+        assert_round_trip(
+            env,
+            RANDOMNESS_DRAWN,
+            RandomnessDrawnEvent {
+                version,
+                dapp: other.clone(),
+                first_index: 4,
+                random_words: words.clone(),
+                fee: 1,
+            },
+            RelinkEvent::RandomnessDrawn,
+        );
+        assert_round_trip(
+            env,
+            TARGET_ADDED,
+            TargetAddedEvent {
+                version,
+                target: other.clone(),
+            },
+            RelinkEvent::TargetAdded,
+        );
+        assert_round_trip(
+            env,
+            TARGET_REMOVED,
+            TargetRemovedEvent {
+                version,
+                target: other.clone(),
+            },
+            RelinkEvent::TargetRemoved,
+        );
+        assert_round_trip(
+            env,
+            RANDOMNESS_FORWARDED,
+            RandomnessForwardedEvent {
+                version,
+                id: id.clone(),
+                target: other.clone(),
+                random_words: words.clone(),
+            },
+            RelinkEvent::RandomnessForwarded,
+        );
+        assert_round_trip(
+            env,
+            FUNCTIONS_REQUESTED,
+            FunctionsRequestedEvent {
+                version,
+                origin: address.clone(),
+                dapp: other.clone(),
+                nonce: 1,
+                id: id.clone(),
+                payload: bytes!(env, 0x0102),
+            },
+            RelinkEvent::FunctionsRequested,
+        );
+        assert_round_trip(
+            env,
+            FUNCTIONS_RESPONDED,
+            FunctionsRespondedEvent {
+                version,
+                id: id.clone(),
+                response: bytes!(env, 0x03),
+                err: bytes!(env, 0x04),
+            },
+            RelinkEvent::FunctionsResponded,
+        );
+        assert_round_trip(
+            env,
+            UPKEEP_REGISTERED,
+            UpkeepRegisteredEvent {
+                version,
+                id: 1,
+                admin: address.clone(),
+                target: other.clone(),
+                interval: 10,
+                max_fee: 5,
+            },
+            RelinkEvent::UpkeepRegistered,
+        );
+        assert_round_trip(
+            env,
+            UPKEEP_FUNDED,
+            UpkeepFundedEvent {
+                version,
+                id: 1,
+                amount: 50,
+            },
+            RelinkEvent::UpkeepFunded,
+        );
+        assert_round_trip(
+            env,
+            UPKEEP_PERFORMED,
+            UpkeepPerformedEvent {
+                version,
+                id: 1,
+                backend: address.clone(),
+                fee: 5,
+            },
+            RelinkEvent::UpkeepPerformed,
+        );
+        assert_round_trip(
+            env,
+            UPKEEP_CANCELLED,
+            UpkeepCancelledEvent {
+                version,
+                id: 1,
+                refund: 45,
+            },
+            RelinkEvent::UpkeepCancelled,
+        );
+        assert_round_trip(
+            env,
+            MESSAGE_RECEIVED,
+            MessageReceivedEvent {
+                version,
+                source_chain: 1,
+                sender: oracle.clone(),
+                nonce: 0,
+                receiver: other.clone(),
+            },
+            RelinkEvent::MessageReceived,
+        );
+        assert_round_trip(
+            env,
+            MESSAGE_SKIPPED,
+            MessageSkippedEvent {
+                version,
+                source_chain: 1,
+                sender: oracle.clone(),
+                nonce: 0,
+            },
+            RelinkEvent::MessageSkipped,
+        );
+        assert_round_trip(
+            env,
+            RECEIVER_SET,
+            ReceiverSetEvent {
+                version,
+                source_chain: 1,
+                sender: oracle.clone(),
+                receiver: other.clone(),
+            },
+            RelinkEvent::ReceiverSet,
+        );
+        assert_round_trip(
+            env,
+            RECEIVER_REMOVED,
+            ReceiverRemovedEvent {
+                version,
+                source_chain: 1,
+                sender: oracle.clone(),
+            },
+            RelinkEvent::ReceiverRemoved,
+        );
+        assert_round_trip(
+            env,
+            INSECURE_MODE_SET,
+            InsecureModeSetEvent {
+                version,
+                enabled: true,
+            },
+            RelinkEvent::InsecureModeSet,
+        );
+        assert_round_trip(
+            env,
+            ORACLE_WEIGHT_SET,
+            OracleWeightSetEvent {
+                version,
+                oracle: key.clone(),
+                weight: 2,
+            },
+            RelinkEvent::OracleWeightSet,
+        );
+        assert_round_trip(
+            env,
+            ORACLE_GROUPS_SET,
+            OracleGroupsSetEvent {
+                version,
+                oracle: key.clone(),
+                groups: vec![env, group.clone()],
+            },
+            RelinkEvent::OracleGroupsSet,
+        );
+        assert_round_trip(
+            env,
+            GROUP_MINIMUM_SET,
+            GroupMinimumSetEvent {
+                version,
+                group,
+                minimum: 1,
+            },
+            RelinkEvent::GroupMinimumSet,
+        );
+    }
+}
@@ -1,28 +1,689 @@
-use soroban_sdk::{symbol_short, Address, Env};
+//! Event schema of the Relink contracts.
+//!
+//! Every event is published with the topics `(name, indexed...)`, where `name` is one of the
+//! symbols below and the indexed topics are the addresses or ids an indexer would filter by.
+//! Values such as fees or thresholds are never part of the topics. The data of every event is a
+//! `#[contracttype]` struct which starts with the schema `version` and contains all fields of the
+//! event, including the indexed ones, so that the data alone is sufficient to decode it.
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Map, Symbol, Vec};
 
-use crate::EthAddress;
+use crate::consumer::{OracleConfig, OracleEntry, OracleKey};
+use crate::eip712::Domain;
+use crate::randomness_results::ResultRetention;
+use crate::{EthAddress, RequestId};
 
-pub fn ownership_transfer_requested(env: &Env, from: &Address, to: &Address) {
-    let topics = (symbol_short!("owner_req"), from, to);
-    env.events().publish(topics, ());
+/// Version of the event schema, part of every event payload.
+pub const EVENT_VERSION: u32 = 1;
+
+pub const OWNERSHIP_TRANSFER_REQUESTED: Symbol = symbol_short!("owner_req");
+pub const OWNERSHIP_TRANSFERRED: Symbol = symbol_short!("owner_acc");
+pub const THRESHOLD_SET: Symbol = symbol_short!("threshold");
+pub const ORACLE_ADDED: Symbol = symbol_short!("oracle_ad");
+pub const ORACLE_REMOVED: Symbol = symbol_short!("oracle_rm");
+pub const ED25519_ORACLE_ADDED: Symbol = symbol_short!("ed_add");
+pub const ED25519_ORACLE_REMOVED: Symbol = symbol_short!("ed_rm");
+pub const PROXY_SET: Symbol = symbol_short!("proxy_set");
+pub const RANDOMNESS_REQUESTED: Symbol = symbol_short!("request");
+pub const RANDOMNESS_PROVIDED: Symbol = symbol_short!("response");
+pub const RANDOMNESS_RECEIVED: Symbol = symbol_short!("rand_recv");
+pub const REQUEST_CANCELLED: Symbol = symbol_short!("cancel");
+pub const CALLBACK_FAILED: Symbol = symbol_short!("cb_fail");
+pub const FEE_SET: Symbol = symbol_short!("fee_set");
+pub const WHITELIST_ADDED: Symbol = symbol_short!("wl_add");
+pub const WHITELIST_REMOVED: Symbol = symbol_short!("wl_rm");
+pub const WITHDRAWN: Symbol = symbol_short!("withdraw");
+pub const PAUSED_SET: Symbol = symbol_short!("paused");
+pub const ORACLE_CONFIG_SET: Symbol = symbol_short!("config");
+pub const OVERLAP_WINDOW_SET: Symbol = symbol_short!("overlap");
+pub const INSECURE_MODE_SET: Symbol = symbol_short!("insecure");
+pub const ORACLE_WEIGHT_SET: Symbol = symbol_short!("oracle_wt");
+pub const ORACLE_GROUPS_SET: Symbol = symbol_short!("oracle_gr");
+pub const GROUP_MINIMUM_SET: Symbol = symbol_short!("group_min");
+pub const MAX_FULFILLMENT_DELAY_SET: Symbol = symbol_short!("max_delay");
+pub const DOMAIN_SET: Symbol = symbol_short!("domain");
+pub const RESULT_RETENTION_SET: Symbol = symbol_short!("retention");
+pub const POOL_CONFIG_SET: Symbol = symbol_short!("pool_cfg");
+pub const POOL_REFILLED: Symbol = symbol_short!("refilled");
+pub const RANDOMNESS_DRAWN: Symbol = symbol_short!("drawn");
+pub const TARGET_ADDED: Symbol = symbol_short!("tgt_add");
+pub const TARGET_REMOVED: Symbol = symbol_short!("tgt_rm");
+pub const RANDOMNESS_FORWARDED: Symbol = symbol_short!("forward");
+pub const FUNCTIONS_REQUESTED: Symbol = symbol_short!("fn_req");
+pub const FUNCTIONS_RESPONDED: Symbol = symbol_short!("fn_resp");
+pub const UPKEEP_REGISTERED: Symbol = symbol_short!("upk_reg");
+pub const UPKEEP_FUNDED: Symbol = symbol_short!("upk_fund");
+pub const UPKEEP_PERFORMED: Symbol = symbol_short!("upk_perf");
+pub const UPKEEP_CANCELLED: Symbol = symbol_short!("upk_can");
+pub const MESSAGE_RECEIVED: Symbol = symbol_short!("msg_recv");
+pub const MESSAGE_SKIPPED: Symbol = symbol_short!("msg_skip");
+pub const RECEIVER_SET: Symbol = symbol_short!("rcv_set");
+pub const RECEIVER_REMOVED: Symbol = symbol_short!("rcv_rm");
+
+/// Topics: `(owner_req, from)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OwnershipTransferRequestedEvent {
+    pub version: u32,
+    pub from: Address,
+    pub to: Address,
+}
+
+/// Topics: `(owner_acc, to)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OwnershipTransferredEvent {
+    pub version: u32,
+    pub from: Address,
+    pub to: Address,
+}
+
+/// Topics: `(threshold)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ThresholdSetEvent {
+    pub version: u32,
+    pub threshold: u32,
+}
+
+/// Topics: `(oracle_ad, oracle)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleAddedEvent {
+    pub version: u32,
+    pub oracle: EthAddress,
+}
+
+/// Topics: `(oracle_rm, oracle)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleRemovedEvent {
+    pub version: u32,
+    pub oracle: EthAddress,
+}
+
+/// Topics: `(ed_add, public_key)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Ed25519OracleAddedEvent {
+    pub version: u32,
+    pub public_key: BytesN<32>,
+}
+
+/// Topics: `(ed_rm, public_key)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Ed25519OracleRemovedEvent {
+    pub version: u32,
+    pub public_key: BytesN<32>,
+}
+
+/// Topics: `(oracle_wt, oracle)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleWeightSetEvent {
+    pub version: u32,
+    pub oracle: OracleKey,
+    pub weight: u32,
+}
+
+/// Topics: `(oracle_gr, oracle)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleGroupsSetEvent {
+    pub version: u32,
+    pub oracle: OracleKey,
+    pub groups: Vec<Symbol>,
+}
+
+/// Topics: `(group_min, group)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct GroupMinimumSetEvent {
+    pub version: u32,
+    pub group: Symbol,
+    pub minimum: u32,
+}
+
+/// Topics: `(proxy_set, proxy)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ProxySetEvent {
+    pub version: u32,
+    pub proxy: Address,
+}
+
+/// Topics: `(request, origin, dapp, nonce)`
+///
+/// Equivalent event to the one in Solidity:
+/// event RandomnessRequest(
+///     address indexed user,
+///     address indexed dapp,
+///     uint256 indexed nonce,
+///     bytes32 requestId,
+///     uint16 _requestConfirmations,
+///     uint32 _numWords
+/// );
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RandomnessRequestedEvent {
+    pub version: u32,
+    pub origin: Address,
+    pub dapp: Address,
+    pub nonce: u128,
+    pub id: RequestId,
+    pub request_confirmations: u32,
+    pub num_words: u32,
+}
+
+/// Topics: `(response, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RandomnessProvidedEvent {
+    pub version: u32,
+    pub id: RequestId,
+    pub backend: Address,
+    pub random_words: Vec<BytesN<32>>,
+}
+
+/// Topics: `(rand_recv, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RandomnessReceivedEvent {
+    pub version: u32,
+    pub id: RequestId,
+    pub random_words: Vec<BytesN<32>>,
+}
+
+/// Topics: `(cancel, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RequestCancelledEvent {
+    pub version: u32,
+    pub id: RequestId,
+}
+
+/// Topics: `(cb_fail, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CallbackFailedEvent {
+    pub version: u32,
+    pub id: RequestId,
+    pub backend: Address,
+}
+
+/// Topics: `(fee_set)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct FeeSetEvent {
+    pub version: u32,
+    pub fee: i128,
+}
+
+/// Topics: `(wl_add, address)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WhitelistAddedEvent {
+    pub version: u32,
+    pub address: Address,
+}
+
+/// Topics: `(wl_rm, address)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WhitelistRemovedEvent {
+    pub version: u32,
+    pub address: Address,
+}
+
+/// Topics: `(withdraw, token)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WithdrawnEvent {
+    pub version: u32,
+    pub token: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// Topics: `(paused)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PausedSetEvent {
+    pub version: u32,
+    pub paused: bool,
+}
+
+/// Topics: `(config, epoch)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OracleConfigSetEvent {
+    pub version: u32,
+    pub epoch: u32,
+    pub oracles: Vec<OracleEntry>,
+    pub threshold: u32,
+    pub group_minimums: Map<Symbol, u32>,
+    pub activation_ledger: u32,
+    pub config_digest: BytesN<32>,
+}
+
+/// Topics: `(overlap)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct OverlapWindowSetEvent {
+    pub version: u32,
+    pub ledgers: u32,
+}
+
+/// Topics: `(max_delay)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MaxFulfillmentDelaySetEvent {
+    pub version: u32,
+    pub ledgers: u32,
+}
+
+/// Topics: `(domain)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct DomainSetEvent {
+    pub version: u32,
+    pub domain: Domain,
+    pub domain_separator: BytesN<32>,
+}
+
+/// Topics: `(retention)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ResultRetentionSetEvent {
+    pub version: u32,
+    pub retention: ResultRetention,
+    pub ttl: u32,
+}
+
+/// Topics: `(pool_cfg)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PoolConfigSetEvent {
+    pub version: u32,
+    pub token: Address,
+    pub draw_fee: i128,
+    pub low_water_mark: u32,
+    pub refill_words: u32,
+    pub refill_fee: i128,
+}
+
+/// Topics: `(refilled, dapp, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PoolRefilledEvent {
+    pub version: u32,
+    pub dapp: Address,
+    pub id: RequestId,
+    pub count: u32,
+    pub available: u32,
+}
+
+/// Topics: `(drawn, dapp)`. `first_index` is the position of the first word among all words ever
+/// drawn by the dapp, so that gaps or repetitions are visible to auditors.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RandomnessDrawnEvent {
+    pub version: u32,
+    pub dapp: Address,
+    pub first_index: u64,
+    pub random_words: Vec<BytesN<32>>,
+    pub fee: i128,
+}
+
+/// Topics: `(tgt_add, target)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TargetAddedEvent {
+    pub version: u32,
+    pub target: Address,
+}
+
+/// Topics: `(tgt_rm, target)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TargetRemovedEvent {
+    pub version: u32,
+    pub target: Address,
+}
+
+/// Topics: `(forward, id, target)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RandomnessForwardedEvent {
+    pub version: u32,
+    pub id: RequestId,
+    pub target: Address,
+    pub random_words: Vec<BytesN<32>>,
+}
+
+/// Topics: `(fn_req, origin, dapp)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct FunctionsRequestedEvent {
+    pub version: u32,
+    pub origin: Address,
+    pub dapp: Address,
+    pub nonce: u128,
+    pub id: RequestId,
+    pub payload: Bytes,
+}
+
+/// Topics: `(fn_resp, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct FunctionsRespondedEvent {
+    pub version: u32,
+    pub id: RequestId,
+    pub response: Bytes,
+    pub err: Bytes,
+}
+
+/// Topics: `(upk_reg, id, admin)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UpkeepRegisteredEvent {
+    pub version: u32,
+    pub id: u64,
+    pub admin: Address,
+    pub target: Address,
+    pub interval: u32,
+    pub max_fee: i128,
+}
+
+/// Topics: `(upk_fund, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UpkeepFundedEvent {
+    pub version: u32,
+    pub id: u64,
+    pub amount: i128,
+}
+
+/// Topics: `(upk_perf, id, backend)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UpkeepPerformedEvent {
+    pub version: u32,
+    pub id: u64,
+    pub backend: Address,
+    pub fee: i128,
+}
+
+/// Topics: `(upk_can, id)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UpkeepCancelledEvent {
+    pub version: u32,
+    pub id: u64,
+    pub refund: i128,
+}
+
+/// Topics: `(msg_recv, source_chain, sender)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MessageReceivedEvent {
+    pub version: u32,
+    pub source_chain: u64,
+    pub sender: EthAddress,
+    pub nonce: u64,
+    pub receiver: Address,
+}
+
+/// Topics: `(msg_skip, source_chain, sender)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MessageSkippedEvent {
+    pub version: u32,
+    pub source_chain: u64,
+    pub sender: EthAddress,
+    pub nonce: u64,
+}
+
+/// Topics: `(rcv_set, source_chain, sender)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReceiverSetEvent {
+    pub version: u32,
+    pub source_chain: u64,
+    pub sender: EthAddress,
+    pub receiver: Address,
+}
+
+/// Topics: `(rcv_rm, source_chain, sender)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReceiverRemovedEvent {
+    pub version: u32,
+    pub source_chain: u64,
+    pub sender: EthAddress,
+}
+
+/// Topics: `(insecure)`
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct InsecureModeSetEvent {
+    pub version: u32,
+    pub enabled: bool,
+}
+
+pub(crate) fn ownership_transfer_requested(env: &Env, from: &Address, to: &Address) {
+    let topics = (OWNERSHIP_TRANSFER_REQUESTED, from);
+    env.events().publish(
+        topics,
+        OwnershipTransferRequestedEvent {
+            version: EVENT_VERSION,
+            from: from.clone(),
+            to: to.clone(),
+        },
+    );
+}
+
+pub(crate) fn ownership_transferred(env: &Env, from: &Address, to: &Address) {
+    let topics = (OWNERSHIP_TRANSFERRED, to);
+    env.events().publish(
+        topics,
+        OwnershipTransferredEvent {
+            version: EVENT_VERSION,
+            from: from.clone(),
+            to: to.clone(),
+        },
+    );
+}
+
+pub(crate) fn threshold_set(env: &Env, threshold: u32) {
+    let topics = (THRESHOLD_SET,);
+    env.events().publish(
+        topics,
+        ThresholdSetEvent {
+            version: EVENT_VERSION,
+            threshold,
+        },
+    );
+}
+
+pub(crate) fn oracle_added(env: &Env, oracle: EthAddress) {
+    let topics = (ORACLE_ADDED, oracle.clone());
+    env.events().publish(
+        topics,
+        OracleAddedEvent {
+            version: EVENT_VERSION,
+            oracle,
+        },
+    );
+}
+
+pub(crate) fn oracle_removed(env: &Env, oracle: EthAddress) {
+    let topics = (ORACLE_REMOVED, oracle.clone());
+    env.events().publish(
+        topics,
+        OracleRemovedEvent {
+            version: EVENT_VERSION,
+            oracle,
+        },
+    );
+}
+
+pub(crate) fn ed25519_oracle_added(env: &Env, public_key: BytesN<32>) {
+    let topics = (ED25519_ORACLE_ADDED, public_key.clone());
+    env.events().publish(
+        topics,
+        Ed25519OracleAddedEvent {
+            version: EVENT_VERSION,
+            public_key,
+        },
+    );
+}
+
+pub(crate) fn ed25519_oracle_removed(env: &Env, public_key: BytesN<32>) {
+    let topics = (ED25519_ORACLE_REMOVED, public_key.clone());
+    env.events().publish(
+        topics,
+        Ed25519OracleRemovedEvent {
+            version: EVENT_VERSION,
+            public_key,
+        },
+    );
+}
+
+pub(crate) fn oracle_weight_set(env: &Env, oracle: OracleKey, weight: u32) {
+    let topics = (ORACLE_WEIGHT_SET, oracle.clone());
+    env.events().publish(
+        topics,
+        OracleWeightSetEvent {
+            version: EVENT_VERSION,
+            oracle,
+            weight,
+        },
+    );
+}
+
+pub(crate) fn oracle_groups_set(env: &Env, oracle: OracleKey, groups: Vec<Symbol>) {
+    let topics = (ORACLE_GROUPS_SET, oracle.clone());
+    env.events().publish(
+        topics,
+        OracleGroupsSetEvent {
+            version: EVENT_VERSION,
+            oracle,
+            groups,
+        },
+    );
+}
+
+pub(crate) fn group_minimum_set(env: &Env, group: Symbol, minimum: u32) {
+    let topics = (GROUP_MINIMUM_SET, group.clone());
+    env.events().publish(
+        topics,
+        GroupMinimumSetEvent {
+            version: EVENT_VERSION,
+            group,
+            minimum,
+        },
+    );
+}
+
+pub(crate) fn oracle_config_set(
+    env: &Env,
+    epoch: u32,
+    config: &OracleConfig,
+    config_digest: &BytesN<32>,
+) {
+    let topics = (ORACLE_CONFIG_SET, epoch);
+    env.events().publish(
+        topics,
+        OracleConfigSetEvent {
+            version: EVENT_VERSION,
+            epoch,
+            oracles: config.oracles.clone(),
+            threshold: config.threshold,
+            group_minimums: config.group_minimums.clone(),
+            activation_ledger: config.activation_ledger,
+            config_digest: config_digest.clone(),
+        },
+    );
+}
+
+pub(crate) fn overlap_window_set(env: &Env, ledgers: u32) {
+    let topics = (OVERLAP_WINDOW_SET,);
+    env.events().publish(
+        topics,
+        OverlapWindowSetEvent {
+            version: EVENT_VERSION,
+            ledgers,
+        },
+    );
+}
+
+pub(crate) fn max_fulfillment_delay_set(env: &Env, ledgers: u32) {
+    let topics = (MAX_FULFILLMENT_DELAY_SET,);
+    env.events().publish(
+        topics,
+        MaxFulfillmentDelaySetEvent {
+            version: EVENT_VERSION,
+            ledgers,
+        },
+    );
+}
+
+pub(crate) fn domain_set(env: &Env, domain: &Domain, domain_separator: &BytesN<32>) {
+    let topics = (DOMAIN_SET,);
+    env.events().publish(
+        topics,
+        DomainSetEvent {
+            version: EVENT_VERSION,
+            domain: domain.clone(),
+            domain_separator: domain_separator.clone(),
+        },
+    );
 }
 
-pub fn ownership_transferred(env: &Env, from: &Address, to: &Address) {
-    let topics = (symbol_short!("owner_acc"), from, to);
-    env.events().publish(topics, ());
+pub(crate) fn result_retention_set(env: &Env, retention: ResultRetention, ttl: u32) {
+    let topics = (RESULT_RETENTION_SET,);
+    env.events().publish(
+        topics,
+        ResultRetentionSetEvent {
+            version: EVENT_VERSION,
+            retention,
+            ttl,
+        },
+    );
 }
 
-pub fn threshold_set(env: &Env, threshold: u32) {
-    let topics = (symbol_short!("threshold"), threshold);
-    env.events().publish(topics, ());
+pub(crate) fn insecure_mode_set(env: &Env, enabled: bool) {
+    let topics = (INSECURE_MODE_SET,);
+    env.events().publish(
+        topics,
+        InsecureModeSetEvent {
+            version: EVENT_VERSION,
+            enabled,
+        },
+    );
 }
 
-pub fn oracle_added(env: &Env, oracle: EthAddress) {
-    let topics = (symbol_short!("oracle_ad"), oracle);
-    env.events().publish(topics, ());
+pub(crate) fn proxy_set(env: &Env, proxy: &Address) {
+    let topics = (PROXY_SET, proxy);
+    env.events().publish(
+        topics,
+        ProxySetEvent {
+            version: EVENT_VERSION,
+            proxy: proxy.clone(),
+        },
+    );
 }
 
-pub fn oracle_removed(env: &Env, oracle: EthAddress) {
-    let topics = (symbol_short!("oracle_rm"), oracle);
-    env.events().publish(topics, ());
+/// Emitted by consumers once a response passed verification.
+pub fn randomness_received(env: &Env, id: RequestId, random_words: Vec<BytesN<32>>) {
+    let topics = (RANDOMNESS_RECEIVED, id.clone());
+    env.events().publish(
+        topics,
+        RandomnessReceivedEvent {
+            version: EVENT_VERSION,
+            id,
+            random_words,
+        },
+    );
 }
@@ -0,0 +1,82 @@
+use soroban_sdk::{bytesn, Address, Bytes, BytesN, Env, Vec};
+
+use crate::consumer::{get_proxy, verify_signatures};
+use crate::eip712::Encoder;
+use crate::{Error, FunctionsProxyClient, RequestId};
+
+/// Implement Solidity equivalent tx input hash:
+/// bytes32 txInputHash = keccak256(
+///     abi.encode(
+///         FUNCTIONS_RESPONSE_HASH,
+///         requestId,
+///         keccak256(response),
+///         keccak256(err),
+///         configDigest
+///     )
+/// );
+pub fn tx_input_hash(
+    env: &Env,
+    id: &RequestId,
+    response: &Bytes,
+    err: &Bytes,
+    config_digest: &BytesN<32>,
+) -> BytesN<32> {
+    // Keccak256 of "FunctionsResponse(bytes32 requestId,bytes response,bytes err,bytes32 configDigest)"
+    let prefix_hash = bytesn!(
+        env,
+        0xa777ef5625bd948547ad625c09d15dd02631fed58226b1f74fde8d88d80e98cb
+    );
+    Encoder::new(env, &prefix_hash)
+        .bytes32(id.as_bytes())
+        .bytes(response)
+        .bytes(err)
+        .bytes32(config_digest)
+        .hash()
+}
+
+pub fn send_request(env: &Env, origin: Address, value: i128, payload: Bytes) -> RequestId {
+    // require authorization by origin
+    origin.require_auth();
+    // call to the proxy contract
+    let proxy_client = FunctionsProxyClient::new(env, &get_proxy(env));
+    proxy_client.send_request(&origin, &value, &env.current_contract_address(), &payload)
+}
+
+pub fn verify_response(
+    env: &Env,
+    id: &RequestId,
+    response: &Bytes,
+    err: &Bytes,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    // must be called by the proxy
+    get_proxy(env).require_auth();
+    // verify oracle signatures
+    verify_signatures(
+        env,
+        |config_digest| tx_input_hash(env, id, response, err, config_digest),
+        signatures,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    extern crate alloc;
+
+    use soroban_sdk::testutils::arbitrary::std::println;
+    use soroban_sdk::Env;
+
+    use crate::testutils::hash_u8;
+
+    /// Precompute values used in tx_input_hash().
+    #[test]
+    fn precompute_hashes() {
+        let env = Env::default();
+        let signature_prefix =
+            b"FunctionsResponse(bytes32 requestId,bytes response,bytes err,bytes32 configDigest)";
+        println!(
+            "FUNCTIONS_RESPONSE_HASH: {}",
+            hash_u8(&env, signature_prefix)
+        );
+    }
+}
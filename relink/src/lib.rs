@@ -1,18 +1,31 @@
 #![no_std]
 
-use soroban_sdk::{contractclient, contracterror, Address, BytesN, Env, Vec};
+use soroban_sdk::{contractclient, contracterror, Address, Bytes, BytesN, Env, Vec};
 
 pub use confirmed_owner::ConfirmedOwner;
+pub use consumer_admin::VrfConsumerAdmin;
+pub use consumer_governance::VrfConsumerGovernance;
 pub use eth_address::EthAddress;
 pub use request_id::RequestId;
+pub use vrf_consumer::FulfillRandomness;
 
 pub mod confirmed_owner;
 pub mod consumer;
+pub mod consumer_admin;
+pub mod consumer_governance;
+pub mod eip712;
 mod eth_address;
-mod events;
+pub mod event_decoder;
+pub mod events;
+pub mod functions;
+pub mod messaging;
+pub mod pending_requests;
+pub mod random;
+pub mod randomness_results;
 mod request_id;
 pub mod testutils;
 pub mod utils;
+pub mod vrf_consumer;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -24,6 +37,35 @@ pub enum Error {
     TooFewSignatures = 4,
     UnauthorizedOracleSignatures = 5,
     UnorderedOracles = 6,
+    InvalidNonce = 7,
+    ReceiverUnknown = 8,
+    UpkeepUnknown = 9,
+    UpkeepNotDue = 10,
+    InsufficientFunds = 11,
+    MaxFeeExceeded = 12,
+    Paused = 13,
+    InvalidThreshold = 14,
+    DuplicateOracle = 15,
+    OracleUnknown = 16,
+    InvalidWeight = 17,
+    GroupQuorumNotMet = 18,
+    InvalidSignerBitmap = 19,
+    RequestIdMismatch = 20,
+    ResponseExpired = 21,
+    RequiredOracleMissing = 22,
+    InvalidSignature = 23,
+    PoolExhausted = 24,
+    InvalidPoolConfig = 25,
+    TargetUnknown = 26,
+    InvalidActivationLedger = 27,
+    DuplicateGroup = 28,
+    InvalidFulfillmentDelay = 29,
+    UnsupportedConfig = 30,
+    InvalidTtl = 31,
+    DappUnknown = 32,
+    InvalidDrawCount = 33,
+    RandomnessRequestFailed = 34,
+    CallbackFailed = 35,
 }
 
 #[contractclient(name = "VrfDirectFundingProxyClient")]
@@ -39,6 +81,12 @@ pub trait VrfDirectFundingProxy {
     ) -> Result<RequestId, Error>;
 
     /// Entry point for randomness data coming from a backend.
+    ///
+    /// A response the consumer rejects reverts the call with the consumer's error, or with
+    /// `CallbackFailed` if the consumer trapped, and the request stays pending. Backends that
+    /// enabled `set_record_failed_callbacks` on the proxy get `Ok` for a rejected response
+    /// instead, it is counted in the statistics and a `cb_fail` event is published in place of
+    /// `response`.
     fn callback_with_randomness(
         env: Env,
         backend: Address,
@@ -46,6 +94,41 @@ pub trait VrfDirectFundingProxy {
         random_words: Vec<BytesN<32>>,
         signatures: Vec<(BytesN<64>, u32)>,
     ) -> Result<(), Error>;
+
+    /// Entry point for randomness data with secp256k1 signatures in the Ethereum formats, either
+    /// 65 byte `r || s || v` or 64 byte EIP-2098 compact signatures. They are converted with
+    /// `eip712::parse_signatures` and delivered like in `callback_with_randomness`, malformed
+    /// signatures fail the call with `InvalidSignature`.
+    fn callback_with_randomness_raw(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<Bytes>,
+    ) -> Result<(), Error>;
+
+    /// Entry point for randomness data, for consumers implementing `VrfDirectFundingConsumerV2`.
+    /// The consumer is passed the origin and nonce of the request and the backend. Rejected
+    /// responses are handled like in `callback_with_randomness`.
+    fn callback_with_randomness_v2(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error>;
+
+    /// Entry point for randomness data in the indexed signature format, for consumers
+    /// implementing `VrfDirectFundingIndexedConsumer`. Rejected responses are handled like in
+    /// `callback_with_randomness`.
+    fn callback_with_randomness_indexed(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signer_bitmap: u128,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error>;
 }
 
 #[contractclient(name = "VrfDirectFundingConsumerClient")]
@@ -57,3 +140,84 @@ pub trait VrfDirectFundingConsumer {
         signatures: Vec<(BytesN<64>, u32)>,
     ) -> Result<(), Error>;
 }
+
+#[contractclient(name = "VrfDirectFundingConsumerV2Client")]
+pub trait VrfDirectFundingConsumerV2 {
+    /// Process a response along with the origin and nonce the request id was derived from and
+    /// the backend which delivered it.
+    fn verify_and_fulfill_randomness_v2(
+        env: Env,
+        id: RequestId,
+        origin: Address,
+        nonce: u128,
+        backend: Address,
+        random_words: Vec<BytesN<32>>,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error>;
+}
+
+#[contractclient(name = "VrfDirectFundingIndexedConsumerClient")]
+pub trait VrfDirectFundingIndexedConsumer {
+    /// Process a response whose signers are given as a bitmap of oracle indexes.
+    fn verify_and_fulfill_indexed(
+        env: Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+        signer_bitmap: u128,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error>;
+}
+
+#[contractclient(name = "FunctionsProxyClient")]
+pub trait FunctionsProxy {
+    /// Generate a new request for off-chain computation with an opaque payload.
+    fn send_request(
+        env: Env,
+        origin: Address,
+        value: i128,
+        dapp: Address,
+        payload: Bytes,
+    ) -> Result<RequestId, Error>;
+
+    /// Entry point for computation results coming from a backend.
+    fn callback_with_response(
+        env: Env,
+        backend: Address,
+        id: RequestId,
+        response: Bytes,
+        err: Bytes,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error>;
+}
+
+#[contractclient(name = "FunctionsConsumerClient")]
+pub trait FunctionsConsumer {
+    fn verify_and_fulfill_response(
+        env: Env,
+        id: RequestId,
+        response: Bytes,
+        err: Bytes,
+        signatures: Vec<(BytesN<64>, u32)>,
+    ) -> Result<(), Error>;
+}
+
+#[contractclient(name = "MessageReceiverClient")]
+pub trait MessageReceiver {
+    /// Handle an authenticated message relayed from an EVM contract. Implementations must require
+    /// authorization by the message receiver contract to make sure the message was verified.
+    fn handle_message(env: Env, source_chain: u64, sender: EthAddress, payload: Bytes);
+}
+
+#[contractclient(name = "RandomnessReceiverClient")]
+pub trait RandomnessReceiver {
+    /// Handle verified random words forwarded by the consumer adapter. Implementations must
+    /// require authorization by the adapter contract to make sure the words were verified.
+    fn receive_randomness(env: Env, id: RequestId, random_words: Vec<BytesN<32>>);
+}
+
+#[contractclient(name = "AutomationTargetClient")]
+pub trait AutomationTarget {
+    /// Perform the scheduled work of an upkeep. Implementations should require authorization by
+    /// the automation proxy contract.
+    fn perform(env: Env, upkeep_id: u64);
+}
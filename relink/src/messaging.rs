@@ -0,0 +1,109 @@
+use soroban_sdk::{bytesn, Bytes, BytesN, Env, Vec};
+
+use crate::consumer::verify_signatures;
+use crate::eip712::Encoder;
+use crate::{Error, EthAddress};
+
+/// Implement Solidity equivalent tx input hash:
+/// bytes32 txInputHash = keccak256(
+///     abi.encode(
+///         CROSS_CHAIN_MESSAGE_HASH,
+///         sourceChain,
+///         sender,
+///         nonce,
+///         keccak256(payload),
+///         configDigest
+///     )
+/// );
+pub fn tx_input_hash(
+    env: &Env,
+    source_chain: u64,
+    sender: &EthAddress,
+    nonce: u64,
+    payload: &Bytes,
+    config_digest: &BytesN<32>,
+) -> BytesN<32> {
+    // Keccak256 of "CrossChainMessage(uint64 sourceChain,address sender,uint64 nonce,bytes payload,bytes32 configDigest)"
+    let prefix_hash = bytesn!(
+        env,
+        0x23e4a8f0f7d69084d210f3923bd2ca2acdd773abef946792842f0de70d6600b0
+    );
+    Encoder::new(env, &prefix_hash)
+        .uint64(source_chain)
+        .address(sender)
+        .uint64(nonce)
+        .bytes(payload)
+        .bytes32(config_digest)
+        .hash()
+}
+
+/// Verify that the message was signed by at least `threshold` of the permitted oracles.
+pub fn verify_message(
+    env: &Env,
+    source_chain: u64,
+    sender: &EthAddress,
+    nonce: u64,
+    payload: &Bytes,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    verify_signatures(
+        env,
+        |config_digest| tx_input_hash(env, source_chain, sender, nonce, payload, config_digest),
+        signatures,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    extern crate alloc;
+
+    use soroban_sdk::testutils::arbitrary::std::println;
+    use soroban_sdk::{bytes, BytesN, Env};
+
+    use crate::messaging::tx_input_hash;
+    use crate::testutils::hash_u8;
+    use crate::EthAddress;
+
+    /// Precompute values used in tx_input_hash().
+    #[test]
+    fn precompute_hashes() {
+        let env = Env::default();
+        let signature_prefix =
+            b"CrossChainMessage(uint64 sourceChain,address sender,uint64 nonce,bytes payload,bytes32 configDigest)";
+        println!(
+            "CROSS_CHAIN_MESSAGE_HASH: {}",
+            hash_u8(&env, signature_prefix)
+        );
+    }
+
+    #[test]
+    fn distinct_hashes() {
+        let env = Env::default();
+        let sender = EthAddress::from_bytes(BytesN::from_array(&env, &[1; 20]));
+        let payload = bytes!(&env, 0x0102);
+        let digest = BytesN::from_array(&env, &[0; 32]);
+        let hash = tx_input_hash(&env, 1, &sender, 0, &payload, &digest);
+        // every field must be part of the hash
+        assert_ne!(hash, tx_input_hash(&env, 2, &sender, 0, &payload, &digest));
+        assert_ne!(
+            hash,
+            tx_input_hash(&env, 1, &EthAddress::zero(&env), 0, &payload, &digest)
+        );
+        assert_ne!(hash, tx_input_hash(&env, 1, &sender, 1, &payload, &digest));
+        assert_ne!(
+            hash,
+            tx_input_hash(&env, 1, &sender, 0, &bytes!(&env, 0x0103), &digest)
+        );
+        assert_ne!(
+            hash,
+            tx_input_hash(
+                &env,
+                1,
+                &sender,
+                0,
+                &payload,
+                &BytesN::from_array(&env, &[1; 32])
+            )
+        );
+    }
+}
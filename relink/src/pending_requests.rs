@@ -0,0 +1,63 @@
+//! Tracking of the requests a consumer is waiting for, so that every response is processed once.
+use soroban_sdk::{contracttype, Env};
+
+use crate::consumer::VerificationPolicy;
+use crate::{consumer, Error, RequestId};
+
+/// Number of ledgers a pending request is kept.
+pub const REQUEST_TTL: u32 = 34560; // 2 days
+
+#[derive(Clone)]
+#[contracttype]
+enum DataKeyPendingRequests {
+    Request(RequestId),
+}
+
+/// Start tracking a request until its response is processed.
+pub fn add(env: &Env, id: RequestId) {
+    add_with_policy(env, id, &VerificationPolicy::new(env));
+}
+
+/// Start tracking a request whose response has to satisfy the policy.
+pub fn add_with_policy(env: &Env, id: RequestId, policy: &VerificationPolicy) {
+    let key = DataKeyPendingRequests::Request(id);
+    env.storage().temporary().set(&key, policy);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, REQUEST_TTL, REQUEST_TTL);
+}
+
+/// Check whether the request is pending.
+pub fn has(env: &Env, id: RequestId) -> bool {
+    let key = DataKeyPendingRequests::Request(id);
+    env.storage().temporary().has(&key)
+}
+
+/// Return the policy which applies to the response of a pending request.
+pub fn policy(env: &Env, id: RequestId) -> Result<VerificationPolicy, Error> {
+    let key = DataKeyPendingRequests::Request(id);
+    env.storage()
+        .temporary()
+        .get(&key)
+        .ok_or(Error::RequestUnknown)
+}
+
+/// Return an error if the request is not pending.
+pub fn require(env: &Env, id: RequestId) -> Result<(), Error> {
+    if !has(env, id) {
+        return Err(Error::RequestUnknown);
+    }
+    Ok(())
+}
+
+/// Return the last ledger sequence at which a response to the request is accepted, if the
+/// consumer has a maximum fulfillment delay.
+pub fn expiry(env: &Env, id: RequestId) -> Option<u32> {
+    consumer::get_response_deadline(env, &id)
+}
+
+/// Stop tracking a request.
+pub fn remove(env: &Env, id: RequestId) {
+    let key = DataKeyPendingRequests::Request(id);
+    env.storage().temporary().remove(&key);
+}
@@ -0,0 +1,217 @@
+//! Deterministic helpers to consume random words without bias. A word can be converted to an
+//! integer directly, expanded into several independent sub-words, or used as the seed of a
+//! `RandomSource` drawing uniform integers, shuffling and picking weighted entries.
+use soroban_sdk::unwrap::UnwrapOptimized;
+use soroban_sdk::{Bytes, BytesN, Env, IntoVal, TryFromVal, Val, Vec, U256};
+
+/// Interpret the last 8 bytes of a word as a big-endian integer.
+pub fn to_u64(word: &BytesN<32>) -> u64 {
+    let bytes = word.to_array();
+    u64::from_be_bytes(bytes[24..].try_into().unwrap_optimized())
+}
+
+/// Interpret the last 16 bytes of a word as a big-endian integer.
+pub fn to_u128(word: &BytesN<32>) -> u128 {
+    let bytes = word.to_array();
+    u128::from_be_bytes(bytes[16..].try_into().unwrap_optimized())
+}
+
+/// Interpret the word as a big-endian 256-bit integer.
+pub fn to_u256(env: &Env, word: &BytesN<32>) -> U256 {
+    U256::from_be_bytes(env, word.as_ref())
+}
+
+/// Derive `count` independent words from one word, `domain` separates the sub-words of
+/// different uses of the same word: `keccak256(word || domain || uint32(index))`.
+pub fn expand(env: &Env, word: &BytesN<32>, domain: &Bytes, count: u32) -> Vec<BytesN<32>> {
+    let mut words = Vec::new(env);
+    for index in 0..count {
+        words.push_back(derive(env, word, domain, index));
+    }
+    words
+}
+
+fn derive(env: &Env, word: &BytesN<32>, domain: &Bytes, index: u32) -> BytesN<32> {
+    let mut buffer = Bytes::from_array(env, &word.to_array());
+    buffer.append(domain);
+    buffer.append(&Bytes::from_array(env, &index.to_be_bytes()));
+    env.crypto().keccak256(&buffer)
+}
+
+/// Stream of random integers derived from a single word.
+pub struct RandomSource {
+    env: Env,
+    seed: BytesN<32>,
+    domain: Bytes,
+    counter: u32,
+}
+
+impl RandomSource {
+    pub fn new(env: &Env, seed: &BytesN<32>) -> Self {
+        Self::with_domain(env, seed, &Bytes::new(env))
+    }
+
+    /// Create a stream that is independent of the streams of other domains with the same seed.
+    pub fn with_domain(env: &Env, seed: &BytesN<32>, domain: &Bytes) -> Self {
+        Self {
+            env: env.clone(),
+            seed: seed.clone(),
+            domain: domain.clone(),
+            counter: 0,
+        }
+    }
+
+    /// Return the next word of the stream, the sub-words of `expand` in order.
+    pub fn next_word(&mut self) -> BytesN<32> {
+        let word = derive(&self.env, &self.seed, &self.domain, self.counter);
+        self.counter += 1;
+        word
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        to_u64(&self.next_word())
+    }
+
+    /// Return a uniformly distributed integer in `[lo, hi)`. Values which would favour the lower
+    /// part of the range are rejected and drawn again.
+    pub fn uniform(&mut self, lo: u64, hi: u64) -> u64 {
+        if lo >= hi {
+            panic!("empty range");
+        }
+        let range = hi - lo;
+        // 2^64 mod range, the number of values to reject
+        let rejected = range.wrapping_neg() % range;
+        loop {
+            let value = self.next_u64();
+            if value >= rejected {
+                return lo + value % range;
+            }
+        }
+    }
+
+    /// Shuffle the vector in place with the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, vec: &mut Vec<T>)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        for i in (1..vec.len()).rev() {
+            let j = self.uniform(0, i as u64 + 1) as u32;
+            if i != j {
+                let item = vec.get_unchecked(i);
+                vec.set(i, vec.get_unchecked(j));
+                vec.set(j, item);
+            }
+        }
+    }
+
+    /// Pick an index with a probability proportional to its weight. Returns `None` if all
+    /// weights are zero.
+    pub fn weighted_index(&mut self, weights: &Vec<u64>) -> Option<u32> {
+        let total = weights.iter().fold(0u64, |total, weight| {
+            total.checked_add(weight).expect("weights overflow")
+        });
+        if total == 0 {
+            return None;
+        }
+        let mut target = self.uniform(0, total);
+        for (index, weight) in weights.iter().enumerate() {
+            if target < weight {
+                return Some(index as u32);
+            }
+            target -= weight;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_sdk::{bytes, bytesn, vec, Env};
+
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let env = Env::default();
+        let word = bytesn!(
+            &env,
+            0x000000000000000000000000000000000000000000000001000000000000002a
+        );
+        assert_eq!(to_u64(&word), 42);
+        assert_eq!(to_u128(&word), (1 << 64) + 42);
+        assert_eq!(to_u256(&env, &word), U256::from_parts(&env, 0, 0, 1, 42));
+    }
+
+    #[test]
+    fn expand_words() {
+        let env = Env::default();
+        let word = BytesN::from_array(&env, &[7; 32]);
+        let words = expand(&env, &word, &bytes!(&env, 0x01), 3);
+        assert_eq!(words.len(), 3);
+        assert_ne!(words.get_unchecked(0), words.get_unchecked(1));
+        // deterministic, and separated by domain
+        assert_eq!(words, expand(&env, &word, &bytes!(&env, 0x01), 3));
+        assert_ne!(
+            words.get_unchecked(0),
+            expand(&env, &word, &bytes!(&env, 0x02), 1).get_unchecked(0)
+        );
+        // the stream yields the expanded words
+        let mut source = RandomSource::with_domain(&env, &word, &bytes!(&env, 0x01));
+        assert_eq!(source.next_word(), words.get_unchecked(0));
+        assert_eq!(source.next_word(), words.get_unchecked(1));
+    }
+
+    #[test]
+    fn uniform() {
+        let env = Env::default();
+        let mut source = RandomSource::new(&env, &BytesN::from_array(&env, &[1; 32]));
+        let mut seen = [false; 6];
+        for _ in 0..100 {
+            let value = source.uniform(10, 16);
+            assert!((10..16).contains(&value));
+            seen[(value - 10) as usize] = true;
+        }
+        assert_eq!(seen, [true; 6]);
+        assert_eq!(source.uniform(5, 6), 5);
+        assert!(source.uniform(0, u64::MAX) < u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn uniform_empty() {
+        let env = Env::default();
+        RandomSource::new(&env, &BytesN::from_array(&env, &[1; 32])).uniform(3, 3);
+    }
+
+    #[test]
+    fn shuffle() {
+        let env = Env::default();
+        let seed = BytesN::from_array(&env, &[2; 32]);
+        let original = vec![&env, 1u32, 2, 3, 4, 5, 6, 7, 8];
+        let mut shuffled = original.clone();
+        RandomSource::new(&env, &seed).shuffle(&mut shuffled);
+        assert_ne!(shuffled, original);
+        // same seed, same permutation
+        let mut again = original.clone();
+        RandomSource::new(&env, &seed).shuffle(&mut again);
+        assert_eq!(shuffled, again);
+        // nothing lost or duplicated
+        for item in original.iter() {
+            assert!(shuffled.contains(item));
+        }
+    }
+
+    #[test]
+    fn weighted_index() {
+        let env = Env::default();
+        let mut source = RandomSource::new(&env, &BytesN::from_array(&env, &[3; 32]));
+        assert_eq!(source.weighted_index(&vec![&env, 0, 0]), None);
+        let weights = vec![&env, 1, 0, 3];
+        let mut counts = [0; 3];
+        for _ in 0..100 {
+            counts[source.weighted_index(&weights).unwrap() as usize] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > counts[0]);
+    }
+}
@@ -0,0 +1,110 @@
+//! Optional store of the random words a consumer received, so that other contracts and user
+//! interfaces can read them after the fulfillment without an indexer.
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::{events, Error, RequestId};
+
+/// Default number of ledgers a stored result is kept after it was written.
+pub const RESULT_TTL: u32 = 518400; // 30 days
+
+/// Where the results are kept, if at all.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ResultRetention {
+    Disabled,
+    Temporary,
+    Persistent,
+}
+
+/// The backend which delivered a response.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum ResultBackend {
+    /// The response was delivered through `callback_with_randomness`, which does not pass the
+    /// backend to the consumer.
+    Unknown,
+    Backend(Address),
+}
+
+/// The random words delivered for a request, with the ledger and the backend which fulfilled it.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RandomnessResult {
+    pub random_words: Vec<BytesN<32>>,
+    pub ledger: u32,
+    pub backend: ResultBackend,
+}
+
+#[derive(Clone)]
+#[contracttype]
+enum DataKeyRandomnessResults {
+    Retention,
+    Ttl,
+    Result(RequestId),
+}
+
+/// Update where results are kept and the number of ledgers they are kept after they were written.
+/// The number of ledgers has to be between 1 and the maximum TTL of the network. Results stored
+/// before are not moved.
+pub fn set_retention(env: &Env, retention: ResultRetention, ttl: u32) -> Result<(), Error> {
+    if ttl == 0 || ttl > env.storage().max_ttl() {
+        return Err(Error::InvalidTtl);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKeyRandomnessResults::Retention, &retention);
+    env.storage()
+        .instance()
+        .set(&DataKeyRandomnessResults::Ttl, &ttl);
+    events::result_retention_set(env, retention, ttl);
+    Ok(())
+}
+
+/// Return where results are kept, nothing is stored by default.
+pub fn get_retention(env: &Env) -> ResultRetention {
+    env.storage()
+        .instance()
+        .get(&DataKeyRandomnessResults::Retention)
+        .unwrap_or(ResultRetention::Disabled)
+}
+
+/// Return the number of ledgers results are kept after they were written.
+pub fn get_ttl(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyRandomnessResults::Ttl)
+        .unwrap_or(RESULT_TTL)
+}
+
+/// Store the result of a fulfilled request according to the retention setting.
+pub fn store(env: &Env, id: RequestId, random_words: &Vec<BytesN<32>>, backend: ResultBackend) {
+    let key = DataKeyRandomnessResults::Result(id);
+    let result = RandomnessResult {
+        random_words: random_words.clone(),
+        ledger: env.ledger().sequence(),
+        backend,
+    };
+    let ttl = get_ttl(env);
+    match get_retention(env) {
+        ResultRetention::Disabled => {}
+        ResultRetention::Temporary => {
+            env.storage().temporary().set(&key, &result);
+            env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        }
+        ResultRetention::Persistent => {
+            env.storage().persistent().set(&key, &result);
+            env.storage().persistent().extend_ttl(&key, ttl, ttl);
+        }
+    }
+}
+
+/// Return the stored result of a request. Reading does not extend its lifetime, otherwise anybody
+/// could keep results alive indefinitely.
+pub fn get(env: &Env, id: RequestId) -> Option<RandomnessResult> {
+    let key = DataKeyRandomnessResults::Result(id);
+    // the retention may have changed since the result was stored, look in both places
+    env.storage()
+        .persistent()
+        .get(&key)
+        .or_else(|| env.storage().temporary().get(&key))
+}
@@ -12,10 +12,10 @@ pub struct RequestId(BytesN<32>);
 impl RequestId {
     pub fn new(env: &Env, origin: &Address, dapp: &Address, proxy: &Address, nonce: u128) -> Self {
         let mut buffer = Bytes::new(env);
-        buffer.append(&env.ledger().network_id().as_ref());
-        buffer.append(&address_bytes(env, &origin));
-        buffer.append(&address_bytes(env, &dapp));
-        buffer.append(&address_bytes(env, &proxy));
+        buffer.append(env.ledger().network_id().as_ref());
+        buffer.append(&address_bytes(env, origin));
+        buffer.append(&address_bytes(env, dapp));
+        buffer.append(&address_bytes(env, proxy));
         buffer.append(&Bytes::from_array(env, &nonce.to_be_bytes()));
         RequestId(env.crypto().keccak256(&buffer))
     }
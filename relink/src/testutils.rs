@@ -4,6 +4,7 @@ extern crate alloc;
 use alloc::format;
 use alloc::string::String;
 
+use ed25519_dalek::{Signer, SigningKey};
 use ethsign::{PublicKey, SecretKey};
 use hex_slice::AsHex;
 use rand::rngs::mock::StepRng;
@@ -13,6 +14,7 @@ use soroban_sdk::testutils::Ledger;
 use soroban_sdk::unwrap::UnwrapOptimized;
 use soroban_sdk::{token, Address, Bytes, BytesN, Env, Vec};
 
+use crate::consumer::{OracleKey, ED25519_SIGNATURE};
 use crate::{EthAddress, RequestId};
 
 pub fn create_token_contract<'a>(
@@ -32,6 +34,12 @@ pub fn advance_ledger(env: &Env, delta: u64) {
     });
 }
 
+pub fn advance_ledger_sequence(env: &Env, delta: u32) {
+    env.ledger().with_mut(|l| {
+        l.sequence_number += delta;
+    });
+}
+
 pub fn hash_hex(hash: &BytesN<32>) -> String {
     format!("0x{:02x}", hash.to_array().plain_hex(false))
 }
@@ -44,6 +52,11 @@ pub fn hash_u8(env: &Env, input: &[u8]) -> String {
     hash_bytes(env, &Bytes::from_slice(env, input))
 }
 
+/// Return the config digest of the configuration the consumer currently uses for verification.
+pub fn active_config_digest(env: &Env, consumer_address: &Address) -> BytesN<32> {
+    env.as_contract(consumer_address, || crate::consumer::get_config_digest(env))
+}
+
 pub struct TestOracle {
     secret: SecretKey,
     public: PublicKey,
@@ -53,6 +66,12 @@ pub struct TestOracleGenerator {
     rng: StepRng,
 }
 
+impl Default for TestOracleGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestOracleGenerator {
     pub fn new() -> Self {
         Self {
@@ -82,7 +101,7 @@ impl TestOracle {
 
     pub fn address(&self, env: &Env) -> EthAddress {
         let raw_bytes = self.public.address();
-        let pub_key = BytesN::from_array(&env, raw_bytes);
+        let pub_key = BytesN::from_array(env, raw_bytes);
         EthAddress::from_bytes(pub_key)
     }
 
@@ -93,18 +112,73 @@ impl TestOracle {
         id: &RequestId,
         random_words: &Vec<BytesN<32>>,
     ) -> (BytesN<64>, u32) {
-        // generate message-to-sign
-        let domain_separator = crate::consumer::domain_separator(&env, consumer_address);
-        let tx_input_hash = crate::consumer::tx_input_hash(&env, id, &random_words);
-        // create message-to-sign
-        // bytes32 totalHash = keccak256(
-        //     abi.encodePacked("\x19\x01", domainSeparator, txInputHash)
-        // );
-        let mut msg = Bytes::from_array(env, &[0x19, 0x01]);
-        msg.append(domain_separator.as_ref());
-        msg.append(tx_input_hash.as_ref());
-        let msg_digest: [u8; 32] = env.crypto().keccak256(&msg).to_array();
-        let signature = self.secret.sign(&msg_digest).unwrap();
+        let config_digest = active_config_digest(env, consumer_address);
+        let tx_input_hash = crate::consumer::tx_input_hash(env, id, random_words, &config_digest);
+        self.sign_typed(env, consumer_address, &tx_input_hash)
+    }
+
+    pub fn sign_response(
+        &self,
+        env: &Env,
+        consumer_address: &Address,
+        id: &RequestId,
+        response: &Bytes,
+        err: &Bytes,
+    ) -> (BytesN<64>, u32) {
+        let config_digest = active_config_digest(env, consumer_address);
+        let tx_input_hash = crate::functions::tx_input_hash(env, id, response, err, &config_digest);
+        self.sign_typed(env, consumer_address, &tx_input_hash)
+    }
+
+    pub fn sign_message(
+        &self,
+        env: &Env,
+        receiver_address: &Address,
+        source_chain: u64,
+        sender: &EthAddress,
+        nonce: u64,
+        payload: &Bytes,
+    ) -> (BytesN<64>, u32) {
+        let config_digest = active_config_digest(env, receiver_address);
+        let tx_input_hash = crate::messaging::tx_input_hash(
+            env,
+            source_chain,
+            sender,
+            nonce,
+            payload,
+            &config_digest,
+        );
+        self.sign_typed(env, receiver_address, &tx_input_hash)
+    }
+
+    /// Sign an update of the consumer's oracles with the active config digest.
+    pub fn sign_config_update(
+        &self,
+        env: &Env,
+        consumer_address: &Address,
+        oracles: &Vec<EthAddress>,
+        threshold: u32,
+        nonce: u64,
+    ) -> (BytesN<64>, u32) {
+        let config_digest = active_config_digest(env, consumer_address);
+        let tx_input_hash =
+            crate::consumer::config_update_hash(env, oracles, threshold, nonce, &config_digest);
+        self.sign_typed(env, consumer_address, &tx_input_hash)
+    }
+
+    pub fn sign_typed(
+        &self,
+        env: &Env,
+        consumer_address: &Address,
+        tx_input_hash: &BytesN<32>,
+    ) -> (BytesN<64>, u32) {
+        let msg_digest = typed_data_digest(env, consumer_address, tx_input_hash);
+        self.sign_digest(env, &BytesN::from_array(env, &msg_digest))
+    }
+
+    /// Sign a raw message digest.
+    pub fn sign_digest(&self, env: &Env, msg_digest: &BytesN<32>) -> (BytesN<64>, u32) {
+        let signature = self.secret.sign(&msg_digest.to_array()).unwrap();
         let mut signature_bytes = Bytes::new(env);
         signature_bytes.append(&Bytes::from_array(env, &signature.r));
         signature_bytes.append(&Bytes::from_array(env, &signature.s));
@@ -115,6 +189,69 @@ impl TestOracle {
     }
 }
 
+/// Test oracle signing with an ed25519 key.
+pub struct TestEd25519Oracle {
+    signing_key: SigningKey,
+}
+
+impl TestEd25519Oracle {
+    pub fn new(seed: &[u8; 32]) -> Self {
+        Self {
+            signing_key: SigningKey::from_bytes(seed),
+        }
+    }
+
+    pub fn public_key(&self, env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, self.signing_key.verifying_key().as_bytes())
+    }
+
+    pub fn key(&self, env: &Env) -> OracleKey {
+        OracleKey::Ed25519(self.public_key(env))
+    }
+
+    /// Sign a randomness response, `index` is the position of the oracle in the configuration.
+    pub fn sign(
+        &self,
+        env: &Env,
+        consumer_address: &Address,
+        index: u32,
+        id: &RequestId,
+        random_words: &Vec<BytesN<32>>,
+    ) -> (BytesN<64>, u32) {
+        let config_digest = active_config_digest(env, consumer_address);
+        let tx_input_hash = crate::consumer::tx_input_hash(env, id, random_words, &config_digest);
+        self.sign_typed(env, consumer_address, index, &tx_input_hash)
+    }
+
+    pub fn sign_typed(
+        &self,
+        env: &Env,
+        consumer_address: &Address,
+        index: u32,
+        tx_input_hash: &BytesN<32>,
+    ) -> (BytesN<64>, u32) {
+        let msg_digest = typed_data_digest(env, consumer_address, tx_input_hash);
+        let signature = self.signing_key.sign(&msg_digest);
+        (
+            BytesN::from_array(env, &signature.to_bytes()),
+            ED25519_SIGNATURE | index,
+        )
+    }
+}
+
+/// Compute the digest oracles sign for the typed data hash.
+fn typed_data_digest(
+    env: &Env,
+    consumer_address: &Address,
+    tx_input_hash: &BytesN<32>,
+) -> [u8; 32] {
+    // generate message-to-sign with the domain the consumer was configured with
+    let domain_separator = env.as_contract(consumer_address, || {
+        crate::consumer::get_domain_separator(env)
+    });
+    crate::eip712::typed_data_digest(env, &domain_separator, tx_input_hash).to_array()
+}
+
 #[cfg(test)]
 mod test {
     use soroban_sdk::testutils::arbitrary::std::println;
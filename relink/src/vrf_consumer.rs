@@ -0,0 +1,435 @@
+//! Building blocks for VRF consumer contracts. `impl_vrf_consumer!` generates the entry points of
+//! a consumer, which only has to implement `FulfillRandomness` to process the random words.
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::consumer::{DomainConfig, VerificationPolicy};
+use crate::randomness_results::{ResultBackend, ResultRetention};
+use crate::{
+    confirmed_owner, consumer, pending_requests, randomness_results, Error, EthAddress, RequestId,
+};
+
+/// Hook receiving the random words of a response.
+pub trait FulfillRandomness {
+    /// Process the random words of a pending request. Only called after the request was found
+    /// and the oracle signatures were verified, the request is no longer pending afterwards.
+    fn fulfill_randomness(
+        env: &Env,
+        id: RequestId,
+        random_words: Vec<BytesN<32>>,
+    ) -> Result<(), Error>;
+}
+
+pub fn initialize(
+    env: &Env,
+    owner: &Address,
+    proxy: &Address,
+    threshold: u32,
+    oracles: Vec<EthAddress>,
+    domain: Option<DomainConfig>,
+) -> Result<(), Error> {
+    confirmed_owner::init(env, owner);
+    consumer::init(env, proxy, threshold, oracles, domain)
+}
+
+pub fn initialize_insecure(env: &Env, owner: &Address, proxy: &Address) -> Result<(), Error> {
+    confirmed_owner::init(env, owner);
+    consumer::init_insecure(env, proxy)
+}
+
+pub fn initiate_randomness_request(env: &Env, origin: Address, value: i128) -> RequestId {
+    let id = consumer::request_randomness(env, origin, value, None, None);
+    // store the request id until the response is processed
+    pending_requests::add(env, id.clone());
+    id
+}
+
+/// Initiate a request whose response has to satisfy the policy, for example more signatures for
+/// requests guarding high values. The policy has to be satisfiable by the active configuration.
+pub fn initiate_randomness_request_with_policy(
+    env: &Env,
+    origin: Address,
+    value: i128,
+    policy: &VerificationPolicy,
+) -> Result<RequestId, Error> {
+    consumer::validate_policy(env, policy)?;
+    let id = consumer::request_randomness(env, origin, value, None, None);
+    // store the request id and its policy until the response is processed
+    pending_requests::add_with_policy(env, id.clone(), policy);
+    Ok(id)
+}
+
+pub fn verify_and_fulfill_randomness<T: FulfillRandomness>(
+    env: &Env,
+    id: RequestId,
+    random_words: Vec<BytesN<32>>,
+    signatures: Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    verify_and_remove(env, &id, &random_words, &signatures)?;
+    // the backend is not known on this path
+    randomness_results::store(env, id.clone(), &random_words, ResultBackend::Unknown);
+    T::fulfill_randomness(env, id, random_words)
+}
+
+/// Like [verify_and_fulfill_randomness], a stored result also records the backend.
+pub fn verify_and_fulfill_randomness_v2<T: FulfillRandomness>(
+    env: &Env,
+    id: RequestId,
+    origin: Address,
+    nonce: u128,
+    backend: Address,
+    random_words: Vec<BytesN<32>>,
+    signatures: Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    // check that the request was made for this consumer
+    consumer::verify_request_id(env, &id, &origin, nonce)?;
+    verify_and_remove(env, &id, &random_words, &signatures)?;
+    randomness_results::store(
+        env,
+        id.clone(),
+        &random_words,
+        ResultBackend::Backend(backend),
+    );
+    T::fulfill_randomness(env, id, random_words)
+}
+
+/// Update where the results of fulfilled requests are kept. Must be authorized by the owner.
+pub fn set_result_retention(env: &Env, retention: ResultRetention, ttl: u32) -> Result<(), Error> {
+    confirmed_owner::require_owner(env);
+    randomness_results::set_retention(env, retention, ttl)
+}
+
+fn verify_and_remove(
+    env: &Env,
+    id: &RequestId,
+    random_words: &Vec<BytesN<32>>,
+    signatures: &Vec<(BytesN<64>, u32)>,
+) -> Result<(), Error> {
+    // check if RequestId exists
+    let policy = pending_requests::policy(env, id.clone())?;
+    // verify signatures
+    consumer::verify_randomness_with_policy(env, id, random_words, signatures, &policy)?;
+    // remove request as it should only be handled once
+    pending_requests::remove(env, id.clone());
+    Ok(())
+}
+
+#[macro_export]
+macro_rules! impl_vrf_consumer {
+    ($type:ident) => {
+        #[contractimpl]
+        impl $type {
+            /// Initialize contract by setting the owner, the proxy address and trusted oracles.
+            /// Without a domain the oracles sign with the default Relink domain.
+            pub fn initialize(
+                env: Env,
+                owner: Address,
+                proxy: Address,
+                threshold: u32,
+                oracles: soroban_sdk::Vec<$crate::EthAddress>,
+                domain: Option<$crate::consumer::DomainConfig>,
+            ) -> Result<(), $crate::Error> {
+                $crate::vrf_consumer::initialize(&env, &owner, &proxy, threshold, oracles, domain)
+            }
+
+            /// Initialize contract without any oracles, responses are accepted without signatures.
+            /// Development only, add oracles and leave insecure mode before going to production.
+            pub fn initialize_insecure(
+                env: Env,
+                owner: Address,
+                proxy: Address,
+            ) -> Result<(), $crate::Error> {
+                $crate::vrf_consumer::initialize_insecure(&env, &owner, &proxy)
+            }
+
+            /// Initiate a request for randomness.
+            pub fn initiate_randomness_request(
+                env: Env,
+                origin: Address,
+                value: i128,
+            ) -> $crate::RequestId {
+                $crate::vrf_consumer::initiate_randomness_request(&env, origin, value)
+            }
+
+            /// Return the last ledger sequence at which a response to the pending request is
+            /// accepted, if the consumer has a maximum fulfillment delay.
+            pub fn get_request_expiry(env: Env, id: $crate::RequestId) -> Option<u32> {
+                $crate::pending_requests::expiry(&env, id)
+            }
+
+            /// Dry run the verification of a response, reporting the outcome of every signature.
+            pub fn diagnose_signatures(
+                env: Env,
+                id: $crate::RequestId,
+                random_words: soroban_sdk::Vec<soroban_sdk::BytesN<32>>,
+                signatures: soroban_sdk::Vec<(soroban_sdk::BytesN<64>, u32)>,
+            ) -> $crate::consumer::SignatureDiagnostics {
+                $crate::consumer::diagnose_signatures(&env, &id, &random_words, &signatures)
+            }
+
+            /// Update where the results of fulfilled requests are kept and the number of ledgers
+            /// they are kept after they were written, at most the maximum TTL of the network.
+            /// Must be authorized by the owner.
+            pub fn set_result_retention(
+                env: Env,
+                retention: $crate::randomness_results::ResultRetention,
+                ttl: u32,
+            ) -> Result<(), $crate::Error> {
+                $crate::vrf_consumer::set_result_retention(&env, retention, ttl)
+            }
+
+            /// Return the random words, fulfillment ledger and backend of a request, if the
+            /// result was stored and has not expired.
+            pub fn get_randomness(
+                env: Env,
+                id: $crate::RequestId,
+            ) -> Option<$crate::randomness_results::RandomnessResult> {
+                $crate::randomness_results::get(&env, id)
+            }
+        }
+
+        #[contractimpl]
+        impl VrfDirectFundingConsumer for $type {
+            /// Process the response to a randomness request.
+            fn verify_and_fulfill_randomness(
+                env: Env,
+                id: $crate::RequestId,
+                random_words: soroban_sdk::Vec<soroban_sdk::BytesN<32>>,
+                signatures: soroban_sdk::Vec<(soroban_sdk::BytesN<64>, u32)>,
+            ) -> Result<(), $crate::Error> {
+                $crate::vrf_consumer::verify_and_fulfill_randomness::<$type>(
+                    &env,
+                    id,
+                    random_words,
+                    signatures,
+                )
+            }
+        }
+
+        #[contractimpl]
+        impl VrfDirectFundingConsumerV2 for $type {
+            /// Process the response to a randomness request made by this consumer.
+            fn verify_and_fulfill_randomness_v2(
+                env: Env,
+                id: $crate::RequestId,
+                origin: Address,
+                nonce: u128,
+                backend: Address,
+                random_words: soroban_sdk::Vec<soroban_sdk::BytesN<32>>,
+                signatures: soroban_sdk::Vec<(soroban_sdk::BytesN<64>, u32)>,
+            ) -> Result<(), $crate::Error> {
+                $crate::vrf_consumer::verify_and_fulfill_randomness_v2::<$type>(
+                    &env,
+                    id,
+                    origin,
+                    nonce,
+                    backend,
+                    random_words,
+                    signatures,
+                )
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_sdk::testutils::Address as AddressTestTrait;
+    use soroban_sdk::{contract, contractimpl, contracttype, vec, String};
+
+    use crate::testutils::TestOracleGenerator;
+    use crate::{pending_requests, VrfDirectFundingConsumer, VrfDirectFundingConsumerV2};
+
+    use super::*;
+
+    #[contract]
+    struct TestContract;
+
+    #[derive(Clone)]
+    #[contracttype]
+    enum DataKey {
+        Fulfilled,
+    }
+
+    impl FulfillRandomness for TestContract {
+        fn fulfill_randomness(
+            env: &Env,
+            _: RequestId,
+            random_words: Vec<BytesN<32>>,
+        ) -> Result<(), Error> {
+            env.storage()
+                .instance()
+                .set(&DataKey::Fulfilled, &random_words);
+            Ok(())
+        }
+    }
+
+    impl_vrf_consumer!(TestContract);
+
+    #[contractimpl]
+    impl TestContract {
+        pub fn fulfilled(env: Env) -> Option<Vec<BytesN<32>>> {
+            env.storage().instance().get(&DataKey::Fulfilled)
+        }
+    }
+
+    #[test]
+    fn fulfill_after_verification() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_address = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract_address);
+        let oracle = &TestOracleGenerator::new().generate_sorted(&env, 1)[0];
+        client.initialize(
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &1,
+            &vec![&env, oracle.address(&env)],
+            &Some(DomainConfig {
+                name: String::from_str(&env, "Test Consumer"),
+                version: String::from_str(&env, "2"),
+                salt: BytesN::from_array(&env, &[1; 32]),
+            }),
+        );
+
+        let id = RequestId::new(
+            &env,
+            &Address::generate(&env),
+            &contract_address,
+            &Address::generate(&env),
+            0,
+        );
+        let random_words = vec![&env, BytesN::from_array(&env, &[7; 32])];
+        let signatures = vec![
+            &env,
+            oracle.sign(&env, &contract_address, &id, &random_words),
+        ];
+
+        // the hook is not called for unknown requests
+        assert_eq!(
+            client.try_verify_and_fulfill_randomness(&id, &random_words, &signatures),
+            Err(Ok(Error::RequestUnknown))
+        );
+        env.as_contract(&contract_address, || {
+            pending_requests::add(&env, id.clone())
+        });
+        // nor for invalid signatures
+        assert_eq!(
+            client.try_verify_and_fulfill_randomness(&id, &random_words, &Vec::new(&env)),
+            Err(Ok(Error::TooFewSignatures))
+        );
+        assert_eq!(client.fulfilled(), None);
+
+        client.verify_and_fulfill_randomness(&id, &random_words, &signatures);
+        assert_eq!(client.fulfilled(), Some(random_words.clone()));
+        // the request is only handled once
+        assert!(
+            !env.as_contract(&contract_address, || pending_requests::has(
+                &env,
+                id.clone()
+            ))
+        );
+        assert_eq!(
+            client.try_verify_and_fulfill_randomness(&id, &random_words, &signatures),
+            Err(Ok(Error::RequestUnknown))
+        );
+    }
+
+    #[test]
+    fn stored_results() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_address = env.register_contract(None, TestContract);
+        let client = TestContractClient::new(&env, &contract_address);
+        let oracle = &TestOracleGenerator::new().generate_sorted(&env, 1)[0];
+        let proxy = Address::generate(&env);
+        client.initialize(
+            &Address::generate(&env),
+            &proxy,
+            &1,
+            &vec![&env, oracle.address(&env)],
+            &None,
+        );
+        let backend = Address::generate(&env);
+        let fulfill = |nonce: u128| {
+            let origin = Address::generate(&env);
+            let id = RequestId::new(&env, &origin, &contract_address, &proxy, nonce);
+            env.as_contract(&contract_address, || {
+                pending_requests::add(&env, id.clone())
+            });
+            let random_words = vec![&env, BytesN::from_array(&env, &[nonce as u8; 32])];
+            let signatures = vec![
+                &env,
+                oracle.sign(&env, &contract_address, &id, &random_words),
+            ];
+            client.verify_and_fulfill_randomness_v2(
+                &id,
+                &origin,
+                &nonce,
+                &backend,
+                &random_words,
+                &signatures,
+            );
+            (id, random_words)
+        };
+
+        // nothing is stored by default
+        let (id, _) = fulfill(0);
+        assert_eq!(client.get_randomness(&id), None);
+
+        // results can't be kept for no time or longer than the network allows
+        assert_eq!(
+            client.try_set_result_retention(&ResultRetention::Temporary, &0),
+            Err(Ok(Error::InvalidTtl))
+        );
+        let max_ttl = env.as_contract(&contract_address, || env.storage().max_ttl());
+        assert_eq!(
+            client.try_set_result_retention(&ResultRetention::Temporary, &(max_ttl + 1)),
+            Err(Ok(Error::InvalidTtl))
+        );
+        client.set_result_retention(&ResultRetention::Temporary, &100);
+        let (id, random_words) = fulfill(1);
+        let expected = randomness_results::RandomnessResult {
+            random_words,
+            ledger: env.ledger().sequence(),
+            backend: ResultBackend::Backend(backend.clone()),
+        };
+        assert_eq!(client.get_randomness(&id), Some(expected.clone()));
+        // results stored before a change of the retention can still be read
+        client.set_result_retention(&ResultRetention::Persistent, &100);
+        assert_eq!(client.get_randomness(&id), Some(expected));
+        let (id, random_words) = fulfill(2);
+        assert_eq!(
+            client.get_randomness(&id).unwrap().random_words,
+            random_words
+        );
+
+        // responses delivered without the backend are stored as well
+        let origin = Address::generate(&env);
+        let id = RequestId::new(&env, &origin, &contract_address, &proxy, 3);
+        env.as_contract(&contract_address, || {
+            pending_requests::add(&env, id.clone())
+        });
+        let random_words = vec![&env, BytesN::from_array(&env, &[3; 32])];
+        let signatures = vec![
+            &env,
+            oracle.sign(&env, &contract_address, &id, &random_words),
+        ];
+        client.verify_and_fulfill_randomness(&id, &random_words, &signatures);
+        let expected = randomness_results::RandomnessResult {
+            random_words,
+            ledger: env.ledger().sequence(),
+            backend: ResultBackend::Unknown,
+        };
+        assert_eq!(client.get_randomness(&id), Some(expected));
+
+        env.as_contract(&contract_address, || {
+            assert_eq!(randomness_results::get_ttl(&env), 100);
+            assert_eq!(
+                randomness_results::get_retention(&env),
+                ResultRetention::Persistent
+            );
+        });
+    }
+}